@@ -0,0 +1,202 @@
+use std::path::{Path, PathBuf};
+
+use crate::{error::LVMError, run_raw_cmd};
+
+/// Represents an entry of the LVM2 devices file (`/etc/lvm/devices/system.devices`)
+///
+/// When the devices file is enabled, LVM2 only uses the devices listed in it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeviceEntry {
+    /// The type of the device ID (e.g. `sys_wwid`, `sys_serial`, `devname`)
+    pub id_type: String,
+
+    /// The device ID, interpreted according to [DeviceEntry::id_type]
+    pub id_name: Option<String>,
+
+    /// The last known path of the device
+    pub device_name: Option<PathBuf>,
+
+    /// The ID of the physical volume on the device
+    pub pvid: Option<String>,
+
+    /// The partition number of the device, if it is a partition
+    pub partition: Option<usize>,
+}
+
+impl DeviceEntry {
+    /// List all entries of the devices file
+    pub fn list() -> Result<Vec<DeviceEntry>, LVMError> {
+        let out = run_raw_cmd("lvmdevices", &[] as &[&str])?;
+
+        out.lines()
+            .map(|line| line.trim())
+            .filter(|line| line.starts_with("Device "))
+            .map(parse_device_entry)
+            .collect()
+    }
+
+    /// Add a device to the devices file
+    pub fn add_device(device: impl AsRef<Path>) -> Result<(), LVMError> {
+        let device = device.as_ref().to_string_lossy();
+        run_raw_cmd("lvmdevices", &["--adddev", &device])?;
+
+        Ok(())
+    }
+
+    /// Add the device containing the physical volume with the specified ID to the devices file
+    pub fn add_pvid(pvid: impl AsRef<str>) -> Result<(), LVMError> {
+        run_raw_cmd("lvmdevices", &["--addpvid", pvid.as_ref()])?;
+
+        Ok(())
+    }
+
+    /// Remove a device from the devices file
+    pub fn delete_device(device: impl AsRef<Path>) -> Result<(), LVMError> {
+        let device = device.as_ref().to_string_lossy();
+        run_raw_cmd("lvmdevices", &["--deldev", &device])?;
+
+        Ok(())
+    }
+
+    /// Remove the device containing the physical volume with the specified ID from the devices file
+    pub fn delete_pvid(pvid: impl AsRef<str>) -> Result<(), LVMError> {
+        run_raw_cmd("lvmdevices", &["--delpvid", pvid.as_ref()])?;
+
+        Ok(())
+    }
+
+    /// Check the devices file for entries which no longer match the system,
+    /// returning the reported problems.
+    pub fn check() -> Result<Vec<String>, LVMError> {
+        let out = run_raw_cmd("lvmdevices", &["--check"])?;
+
+        Ok(out
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| line.into())
+            .collect())
+    }
+
+    /// Update the devices file so that its entries match the system
+    pub fn update() -> Result<(), LVMError> {
+        run_raw_cmd("lvmdevices", &["--update"])?;
+
+        Ok(())
+    }
+}
+
+/// Parse a single line of `lvmdevices` output
+///
+/// Entries are printed as `Device <path> IDTYPE=<..> IDNAME=<..> DEVNAME=<..> PVID=<..> [PART=<..>]`
+fn parse_device_entry(line: &str) -> Result<DeviceEntry, LVMError> {
+    let mut id_type = None;
+    let mut id_name = None;
+    let mut device_name = None;
+    let mut pvid = None;
+    let mut partition = None;
+
+    for (key, value) in line.split_whitespace().filter_map(|p| p.split_once('=')) {
+        // LVM2 uses placeholders for unknown values
+        if matches!(value, "" | "." | "none") {
+            continue;
+        }
+
+        match key {
+            "IDTYPE" => id_type = Some(value.into()),
+            "IDNAME" => id_name = Some(value.into()),
+            "DEVNAME" => device_name = Some(value.into()),
+            "PVID" => pvid = Some(value.into()),
+            "PART" => {
+                partition = Some(value.parse().map_err(|_| LVMError::MalformedOutput {
+                    cause: "invalid partition number in devices file entry".into(),
+                    result: line.into(),
+                })?)
+            }
+            _ => (),
+        }
+    }
+
+    Ok(DeviceEntry {
+        id_type: id_type.ok_or(LVMError::MalformedOutput {
+            cause: "devices file entry is missing its ID type".into(),
+            result: line.into(),
+        })?,
+        id_name,
+        device_name,
+        pvid,
+        partition,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // In the format printed by `lvmdevices` on LVM2 2.03
+    const OUTPUT: &str = "  Device /dev/sdb IDTYPE=sys_wwid IDNAME=naa.6001405a8c1f4b3d9e2a7c4b5d6e7f80 DEVNAME=/dev/sdb PVID=Xq4S1zUu8eWmHn0pKdT3aLbV7cYr2GfJ
+  Device /dev/nvme0n1p3 IDTYPE=sys_wwid IDNAME=eui.0025388b91b2c3d4 DEVNAME=/dev/nvme0n1p3 PVID=m2N8vB4xC6zL0kJ5hG7fD3sA9qW1eR3t PART=3
+  Device /dev/md0 IDTYPE=md_uuid IDNAME=0f3a9c1e:7b2d4f60:a1c3e5b7:d9f1a3c5 DEVNAME=/dev/md0 PVID=.
+  Device /dev/vdc IDTYPE=devname IDNAME=/dev/vdc DEVNAME=/dev/vdc PVID=none
+  Device . IDTYPE=sys_serial IDNAME=QM00003 DEVNAME=. PVID=Tz5Y8uI1oP3aS6dF9gH2jK4lZ7xC0vBn
+";
+
+    fn entries() -> Vec<DeviceEntry> {
+        OUTPUT
+            .lines()
+            .map(|line| line.trim())
+            .map(|line| parse_device_entry(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn parses_entries() {
+        let entries = entries();
+
+        assert_eq!(
+            entries[0],
+            DeviceEntry {
+                id_type: "sys_wwid".into(),
+                id_name: Some("naa.6001405a8c1f4b3d9e2a7c4b5d6e7f80".into()),
+                device_name: Some("/dev/sdb".into()),
+                pvid: Some("Xq4S1zUu8eWmHn0pKdT3aLbV7cYr2GfJ".into()),
+                partition: None,
+            }
+        );
+
+        assert_eq!(entries[1].partition, Some(3));
+        assert_eq!(
+            entries[2].id_name.as_deref(),
+            Some("0f3a9c1e:7b2d4f60:a1c3e5b7:d9f1a3c5")
+        );
+    }
+
+    #[test]
+    fn skips_placeholders() {
+        let entries = entries();
+
+        assert_eq!(entries[2].pvid, None);
+        assert_eq!(entries[3].pvid, None);
+        assert_eq!(entries[4].device_name, None);
+        assert_eq!(
+            entries[4].pvid.as_deref(),
+            Some("Tz5Y8uI1oP3aS6dF9gH2jK4lZ7xC0vBn")
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_entries() {
+        let missing_type = "Device /dev/sdb IDNAME=naa.6001405a8c1f4b3d DEVNAME=/dev/sdb PVID=.";
+        assert!(matches!(
+            parse_device_entry(missing_type),
+            Err(LVMError::MalformedOutput { .. })
+        ));
+
+        let invalid_part =
+            "Device /dev/sdb1 IDTYPE=devname IDNAME=/dev/sdb1 DEVNAME=/dev/sdb1 PVID=. PART=one";
+        assert!(matches!(
+            parse_device_entry(invalid_part),
+            Err(LVMError::MalformedOutput { .. })
+        ));
+    }
+}
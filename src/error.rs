@@ -18,6 +18,29 @@ pub enum LVMError {
 
     /// Represents an error in finding a specified resource
     NotFound { resource: String },
+
+    /// Represents a request that was rejected before running any LVM2 command
+    InvalidRequest { reason: String },
+
+    /// Represents an attempt to use a shared volume group while lvmlockd is unavailable
+    LockManagerUnavailable { message: String },
+
+    /// Represents an attempt to access a volume group owned by another host
+    ForeignVolumeGroup {
+        volume_group: String,
+        system_id: String,
+    },
+
+    /// Represents a request for more space than a volume group can allocate
+    InsufficientSpace {
+        volume_group: String,
+        required_bytes: usize,
+        available_bytes: usize,
+        shortfall_bytes: usize,
+    },
+
+    /// Represents a snapshot merge which LVM2 reported as failed
+    SnapshotMergeFailed { origin: String, snapshot: String },
 }
 
 impl Error for LVMError {}
@@ -34,13 +57,38 @@ impl Display for LVMError {
                 "could not run `{}` with args `{:?}`: {}",
                 command, args, message
             ),
-            Self::Internal { io } => write!(f, "could not run lvm command: {}", io.to_string()),
+            Self::Internal { io } => write!(f, "could not run lvm command: {}", io),
             Self::MalformedOutput { cause, result } => write!(
                 f,
                 "output of lvm command is malformed: {} -> {}",
                 cause, result
             ),
             Self::NotFound { resource } => write!(f, "requested resource not found: {}", resource),
+            Self::InvalidRequest { reason } => write!(f, "invalid request: {}", reason),
+            Self::LockManagerUnavailable { message } => {
+                write!(f, "lvmlockd is not available: {}", message)
+            }
+            Self::ForeignVolumeGroup {
+                volume_group,
+                system_id,
+            } => write!(
+                f,
+                "volume group {} is owned by another host (system ID {})",
+                volume_group, system_id
+            ),
+            Self::InsufficientSpace {
+                volume_group,
+                required_bytes,
+                available_bytes,
+                shortfall_bytes,
+            } => write!(
+                f,
+                "volume group {} cannot allocate {}B ({}B available, {}B short)",
+                volume_group, required_bytes, available_bytes, shortfall_bytes
+            ),
+            Self::SnapshotMergeFailed { origin, snapshot } => {
+                write!(f, "merging snapshot {} into {} failed", snapshot, origin)
+            }
         }
     }
 }
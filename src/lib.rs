@@ -2,8 +2,12 @@ mod resource_capacity;
 mod resource_name;
 mod resource_uuid;
 
+pub mod devices;
 pub mod error;
 pub mod lv;
+pub mod metadata;
+pub mod pv;
+pub mod scan;
 pub mod vg;
 
 pub use resource_capacity::*;
@@ -15,11 +19,18 @@ use std::path::PathBuf;
 use std::process::Command;
 
 use error::LVMError;
-use serde::de::DeserializeOwned;
+use regex::Regex;
+use serde::{
+    de::{DeserializeOwned, IntoDeserializer},
+    Deserialize, Deserializer,
+};
 
 lazy_static::lazy_static! {
     // TODO: Allow an env var to specify this
     static ref LVM_COMMAND: PathBuf = which::which("lvm").expect("could not locate lvm binary!");
+
+    static ref LVMLOCKD_REGEX: Regex = Regex::new("(requires lvmlockd|lvmlockd (process )?(is )?not running|check that lvmlockd is running)").expect("could not compile lvmlockd regex!");
+    static ref FOREIGN_VG_REGEX: Regex = Regex::new("Cannot access VG (\\S+) with system ID (\\S+) with").expect("could not compile foreign volume group regex!");
 }
 
 /// Trait that represents a struct that can be deserialized from a single character
@@ -68,6 +79,19 @@ pub enum AllocationPolicy {
     Normal { locked: bool },
 }
 
+impl AllocationPolicy {
+    /// The value of this policy as accepted by the `--alloc` argument
+    pub(crate) fn as_arg(&self) -> &'static str {
+        match self {
+            AllocationPolicy::Anyware { .. } => "anywhere",
+            AllocationPolicy::Contiguous { .. } => "contiguous",
+            AllocationPolicy::Inherited { .. } => "inherit",
+            AllocationPolicy::Cling { .. } => "cling",
+            AllocationPolicy::Normal { .. } => "normal",
+        }
+    }
+}
+
 impl TryFromChar for AllocationPolicy {
     fn try_from_char(c: char) -> Result<Self, Box<dyn std::error::Error>> {
         let res = match c {
@@ -88,6 +112,58 @@ impl TryFromChar for AllocationPolicy {
     }
 }
 
+/// Deserialize an optional report field, where LVM2 reports a missing value as an empty string
+pub(crate) fn deserialize_optional<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    if s.is_empty() {
+        return Ok(None);
+    }
+
+    T::deserialize(IntoDeserializer::<D::Error>::into_deserializer(s)).map(Some)
+}
+
+/// Deserialize a binary report field, which LVM2 reports as either its name or empty
+pub(crate) fn deserialize_flag<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+
+    Ok(!s.is_empty() && s != "0")
+}
+
+/// Deserialize an optional percentage, where LVM2 reports a missing value as an empty string
+pub(crate) fn deserialize_percent<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    if s.is_empty() {
+        return Ok(None);
+    }
+
+    s.parse()
+        .map(Some)
+        .map_err(|_| serde::de::Error::custom(format!("invalid percentage: {}", s)))
+}
+
+/// Deserialize a comma-separated list of tags
+pub(crate) fn deserialize_tags<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+
+    Ok(s.split(',')
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| tag.into())
+        .collect())
+}
+
 /// Runs a command and then unwraps the results, converting it into the templated output
 pub(crate) fn run_cmd<T>(
     cmd: impl AsRef<str>,
@@ -98,54 +174,15 @@ where
     T: DeserializeOwned + std::fmt::Debug,
 {
     const DEFAULT_ARGS: [&str; 2] = ["--reportformat", "json"];
-    let mut base = Command::new(&*LVM_COMMAND);
 
-    // Run the command
-    let out = base.arg(cmd.as_ref()).args(&DEFAULT_ARGS).args(args);
-
-    #[cfg(feature = "logging")]
-    log::info!(
-        "Running command {:?} with args {:?}",
-        out.get_program(),
-        out.get_args()
-    );
-
-    let out = out.output().map_err(|err| LVMError::Internal { io: err })?;
-
-    if !out.status.success() {
-        return Err(match out.status.code().unwrap_or_default() {
-            5 => LVMError::NotFound {
-                resource: base
-                    .get_args()
-                    .into_iter()
-                    .last()
-                    .unwrap_or_default()
-                    .to_string_lossy()
-                    .into(),
-            },
-            _ => LVMError::Command {
-                command: cmd.as_ref().into(),
-                args: base
-                    .get_args()
-                    .into_iter()
-                    .map(|arg| arg.to_string_lossy().into())
-                    .collect(),
-                message: String::from_utf8_lossy(&out.stderr).trim().into(),
-            },
-        });
-    }
-
-    // Unwrap the report
-    let wrapped = std::str::from_utf8(&out.stdout).map_err(|err| LVMError::MalformedOutput {
-        cause: "could not decode command output from UTF-8".into(),
-        result: err.to_string(),
-    })?;
+    // Run the command and unwrap the report
+    let wrapped = exec_cmd(cmd, &DEFAULT_ARGS, args)?;
 
     #[cfg(feature = "logging")]
     log::debug!("Command executed with: {}", wrapped);
 
     let unwrapped = if let Some(wrapping) = &outer_key {
-        let unwrapped = serde_json::from_str::<serde_json::Value>(wrapped)
+        let unwrapped = serde_json::from_str::<serde_json::Value>(&wrapped)
             .map_err(|e| LVMError::MalformedOutput {
                 cause: "could not decode JSON output".into(),
                 result: e.to_string(),
@@ -164,7 +201,7 @@ where
             .and_then(|unwrapped| {
                 unwrapped
                     .as_array()
-                    .map(|v| v.clone())
+                    .cloned()
                     .ok_or(LVMError::MalformedOutput {
                         cause: "wrapped value is not an array".into(),
                         result: "expecting [ ... ]".into(),
@@ -194,3 +231,150 @@ where
 
     Ok(as_type)
 }
+
+/// Runs a command which does not support JSON reports, returning its raw output
+pub(crate) fn run_raw_cmd(
+    cmd: impl AsRef<str>,
+    args: &[impl AsRef<OsStr>],
+) -> Result<String, LVMError> {
+    exec_cmd(cmd, &[], args)
+}
+
+/// Runs a command, mapping any failure into an [LVMError]
+fn exec_cmd(
+    cmd: impl AsRef<str>,
+    default_args: &[&str],
+    args: &[impl AsRef<OsStr>],
+) -> Result<String, LVMError> {
+    let mut base = Command::new(&*LVM_COMMAND);
+
+    // Run the command
+    let out = base.arg(cmd.as_ref()).args(default_args).args(args);
+
+    #[cfg(feature = "logging")]
+    log::info!(
+        "Running command {:?} with args {:?}",
+        out.get_program(),
+        out.get_args()
+    );
+
+    let out = out.output().map_err(|err| LVMError::Internal { io: err })?;
+
+    if !out.status.success() {
+        let args: Vec<String> = base
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into())
+            .collect();
+
+        return Err(map_cmd_error(
+            cmd.as_ref(),
+            args,
+            out.status.code(),
+            &String::from_utf8_lossy(&out.stderr),
+        ));
+    }
+
+    String::from_utf8(out.stdout).map_err(|err| LVMError::MalformedOutput {
+        cause: "could not decode command output from UTF-8".into(),
+        result: err.to_string(),
+    })
+}
+
+/// Map the exit code and error output of a failed command into an [LVMError]
+fn map_cmd_error(cmd: &str, args: Vec<String>, code: Option<i32>, stderr: &str) -> LVMError {
+    // Some failures are reported with a generic exit code, so check the message first
+    if let Some(captures) = FOREIGN_VG_REGEX.captures(stderr) {
+        return LVMError::ForeignVolumeGroup {
+            volume_group: captures[1].into(),
+            system_id: captures[2].trim_end_matches('.').into(),
+        };
+    }
+
+    if LVMLOCKD_REGEX.is_match(stderr) {
+        return LVMError::LockManagerUnavailable {
+            message: stderr.trim().into(),
+        };
+    }
+
+    match code.unwrap_or_default() {
+        5 => LVMError::NotFound {
+            resource: args.last().cloned().unwrap_or_default(),
+        },
+        _ => LVMError::Command {
+            command: cmd.into(),
+            args,
+            message: stderr.trim().into(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(args: &[&str], code: i32, stderr: &str) -> LVMError {
+        let cmd = args[0];
+        let args = args.iter().map(|arg| arg.to_string()).collect();
+
+        map_cmd_error(cmd, args, Some(code), stderr)
+    }
+
+    #[test]
+    fn maps_foreign_volume_group() {
+        let stderr = "  Cannot access VG vg1 with system ID host2 with local system ID host1.\n";
+
+        match map(&["vgs", "vg1"], 5, stderr) {
+            LVMError::ForeignVolumeGroup {
+                volume_group,
+                system_id,
+            } => {
+                assert_eq!(volume_group, "vg1");
+                assert_eq!(system_id, "host2");
+            }
+            other => panic!("expected a foreign volume group, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn maps_unavailable_lock_manager() {
+        let samples = [
+            "  WARNING: lvmlockd process is not running.\n  Reading without shared global lock.\n  Cannot access VG vg1 due to failed lock.\n",
+            "  VG vg1 lock skipped: lvmlockd not running.\n  Cannot access VG vg1 due to failed lock.\n",
+            "  Using a shared lock type requires lvmlockd.\n  Run `vgcreate --help' for more information.\n",
+            "  Global lock failed: check that lvmlockd is running.\n",
+        ];
+
+        for stderr in samples {
+            match map(&["vgs", "vg1"], 5, stderr) {
+                LVMError::LockManagerUnavailable { message } => {
+                    assert_eq!(message, stderr.trim())
+                }
+                other => panic!("expected lvmlockd to be unavailable, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn maps_exit_codes() {
+        let stderr =
+            "  Volume group \"missing\" not found\n  Cannot process volume group missing\n";
+        match map(&["vgs", "--reportformat", "json", "missing"], 5, stderr) {
+            LVMError::NotFound { resource } => assert_eq!(resource, "missing"),
+            other => panic!("expected not found, got {:?}", other),
+        }
+
+        let stderr = "  Logical volume vg1/data contains a filesystem in use.\n";
+        match map(&["lvremove", "vg1/data"], 3, stderr) {
+            LVMError::Command {
+                command,
+                args,
+                message,
+            } => {
+                assert_eq!(command, "lvremove");
+                assert_eq!(args, vec!["lvremove", "vg1/data"]);
+                assert_eq!(message, stderr.trim());
+            }
+            other => panic!("expected a command error, got {:?}", other),
+        }
+    }
+}
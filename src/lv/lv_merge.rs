@@ -0,0 +1,123 @@
+use crate::{
+    error::LVMError,
+    lv::{LVState, LVVolumeType, LogicalVolume},
+    run_raw_cmd, ResourceName,
+};
+
+/// Represents a snapshot being merged back into its origin
+#[derive(Clone, Debug)]
+pub struct SnapshotMerge {
+    pub volume_group_name: ResourceName,
+    pub origin_name: ResourceName,
+    pub snapshot_name: ResourceName,
+
+    /// Iff the merge was delayed until the next activation of the origin, since
+    /// the origin was open when the merge was requested
+    pub is_deferred: bool,
+
+    /// The copy-on-write usage of the snapshot when the merge was requested
+    initial_percent: Option<f64>,
+}
+
+/// The progress of a [SnapshotMerge]
+#[derive(Clone, Debug, PartialEq)]
+pub enum MergeProgress {
+    /// The merge has not started yet, usually until the next activation of the origin
+    Deferred,
+
+    /// The merge is running, with the percentage of changes merged so far
+    InProgress { percent: f64 },
+
+    /// The snapshot has been fully merged and removed
+    Complete,
+}
+
+impl LogicalVolume {
+    /// Merge the snapshot back into its origin, rolling the origin back to the
+    /// state captured by the snapshot. Works for both thick and thin snapshots.
+    ///
+    /// If the origin is open, LVM2 defers the merge until the origin is next
+    /// activated. The snapshot is removed once the merge completes.
+    pub fn merge(self) -> Result<SnapshotMerge, LVMError> {
+        let Some(origin_name) = self.snapshot_origin() else {
+            return Err(LVMError::InvalidRequest {
+                reason: format!("{} is not a snapshot", self.id()),
+            });
+        };
+
+        check_merge_state(&self, &origin_name)?;
+
+        // Return once the merge has started, rather than waiting for it to finish
+        let out = run_raw_cmd("lvconvert", &["--merge", "--background", &self.id()])?;
+        let is_deferred = out.contains("Delaying merge") || out.contains("on next activation");
+
+        Ok(SnapshotMerge {
+            volume_group_name: self.volume_group_name,
+            origin_name,
+            snapshot_name: self.name,
+            is_deferred,
+            initial_percent: self.snapshot_percent,
+        })
+    }
+}
+
+impl SnapshotMerge {
+    /// Check the progress of the merge
+    pub fn poll(&self) -> Result<MergeProgress, LVMError> {
+        let origin = LogicalVolume::from_id(&self.volume_group_name, &self.origin_name)?;
+        check_merge_state(&origin, &self.snapshot_name)?;
+
+        // The snapshot disappears once it has been merged
+        let snapshot = match LogicalVolume::from_id(&self.volume_group_name, &self.snapshot_name) {
+            Ok(snapshot) => snapshot,
+            Err(LVMError::NotFound { .. }) => return Ok(MergeProgress::Complete),
+            Err(err) => return Err(err),
+        };
+
+        check_merge_state(&snapshot, &self.origin_name)?;
+
+        let LVVolumeType::Origin {
+            merging_snapshot: true,
+        } = origin.attributes.volume_type
+        else {
+            // Thin merges only start on activation, and complete at once. The merge
+            // is only complete once the snapshot is gone, even if a finished thick
+            // merge is about to remove it.
+            return Ok(MergeProgress::Deferred);
+        };
+
+        // The origin is flagged as merging as soon as the merge is requested, so
+        // a deferred merge only starts once the snapshot's usage starts dropping
+        let current = snapshot.snapshot_percent.unwrap_or_default();
+        let initial = self.initial_percent.unwrap_or_default();
+        if origin.attributes.state != LVState::Active || (self.is_deferred && current >= initial) {
+            return Ok(MergeProgress::Deferred);
+        }
+
+        let percent = if initial > 0.0 {
+            ((initial - current) / initial * 100.0).clamp(0.0, 100.0)
+        } else {
+            0.0
+        };
+
+        Ok(MergeProgress::InProgress { percent })
+    }
+}
+
+/// Surface a failed merge reported in the state of either side of it
+fn check_merge_state(lv: &LogicalVolume, other: &ResourceName) -> Result<(), LVMError> {
+    if let LVState::SnapshotMergeFailed { .. } = lv.attributes.state {
+        let (origin, snapshot) = if lv.origin.is_some() {
+            (other.to_string(), lv.name.to_string())
+        } else {
+            (lv.name.to_string(), other.to_string())
+        };
+
+        return Err(LVMError::SnapshotMergeFailed {
+            origin: format!("{}/{}", lv.volume_group_name, origin),
+            snapshot: format!("{}/{}", lv.volume_group_name, snapshot),
+        });
+    }
+
+    Ok(())
+}
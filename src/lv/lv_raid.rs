@@ -0,0 +1,257 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Deserializer};
+
+use crate::{
+    deserialize_optional, deserialize_percent, error::LVMError, lv::LogicalVolume, run_cmd,
+    ResourceName,
+};
+
+/// Represents a RAID [LogicalVolume] along with its synchronization state
+#[derive(Clone, Debug, Deserialize)]
+pub struct RaidVolume {
+    #[serde(flatten)]
+    pub volume: LogicalVolume,
+
+    /// The percentage of the volume which is in sync, if active
+    #[serde(deserialize_with = "deserialize_percent")]
+    pub sync_percent: Option<f64>,
+
+    /// The synchronization currently running, if active
+    #[serde(rename = "raid_sync_action", deserialize_with = "deserialize_optional")]
+    pub sync_action: Option<RaidSyncAction>,
+
+    /// The amount of inconsistent sectors found by the last scrub, if active
+    #[serde(
+        rename = "raid_mismatch_count",
+        deserialize_with = "deserialize_optional_count"
+    )]
+    pub mismatch_count: Option<usize>,
+
+    #[serde(rename = "lv_health_status")]
+    pub health_status: LVHealthStatus,
+}
+
+/// The synchronization running on a [RaidVolume]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RaidSyncAction {
+    /// All images are in sync, and nothing is running
+    Idle,
+
+    /// Synchronization is suspended
+    Frozen,
+
+    /// The images are being synchronized, such as after creation
+    Resync,
+
+    /// A replaced image is being rebuilt
+    Recover,
+
+    /// The images are being scrubbed for inconsistencies, without fixing them
+    Check,
+
+    /// The images are being scrubbed, fixing any inconsistencies
+    Repair,
+
+    /// The layout of the volume is being changed
+    Reshape,
+}
+
+/// The health of a [LogicalVolume], as reported by LVM2
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LVHealthStatus {
+    Healthy,
+
+    /// One or more images are missing
+    Partial,
+
+    /// An image has had a transient failure, and the volume should be refreshed
+    RefreshNeeded,
+
+    /// A scrub found inconsistencies between the images
+    MismatchesExist,
+
+    /// The volume has failed
+    Failed,
+
+    /// Any other status, such as those reported for thin pools and caches
+    Other(String),
+}
+
+impl<'de> Deserialize<'de> for LVHealthStatus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+
+        Ok(match s.as_str() {
+            "" => LVHealthStatus::Healthy,
+            "partial" => LVHealthStatus::Partial,
+            "refresh needed" => LVHealthStatus::RefreshNeeded,
+            "mismatches exist" => LVHealthStatus::MismatchesExist,
+            "failed" => LVHealthStatus::Failed,
+            _ => LVHealthStatus::Other(s),
+        })
+    }
+}
+
+/// The kind of scrub to run on a [RaidVolume]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScrubMode {
+    /// Only count inconsistencies
+    Check,
+
+    /// Count and fix inconsistencies
+    Repair,
+}
+
+/// Represents a synchronization running on a [RaidVolume], such as a scrub or a rebuild
+#[derive(Clone, Debug)]
+pub struct RaidSync {
+    pub volume_group_name: ResourceName,
+    pub name: ResourceName,
+}
+
+/// The progress of a [RaidSync]
+#[derive(Clone, Debug, PartialEq)]
+pub enum RaidSyncProgress {
+    InProgress {
+        action: RaidSyncAction,
+        percent: f64,
+    },
+
+    /// The synchronization is done
+    Complete {
+        /// The amount of inconsistent sectors found by the last scrub, as the
+        /// kernel resets the count whenever a check or repair starts
+        mismatch_count: usize,
+    },
+}
+
+impl RaidVolume {
+    /// Get a [RaidVolume] from its ID pair (volume group / name)
+    pub fn from_id(
+        volume_group: &ResourceName,
+        name: &ResourceName,
+    ) -> Result<RaidVolume, LVMError> {
+        let id = format!("{}/{}", volume_group, name);
+
+        query(&id)?.pop().ok_or(LVMError::NotFound { resource: id })
+    }
+
+    /// List all [RaidVolume]s of a volume group
+    pub fn list_for_vg(volume_group: &ResourceName) -> Result<Vec<RaidVolume>, LVMError> {
+        query(volume_group)
+    }
+
+    /// Start scrubbing the [RaidVolume], returning a handle to follow its progress.
+    ///
+    /// Note: The [RaidVolume] must be active and fully synchronized.
+    pub fn scrub(&self, mode: ScrubMode) -> Result<RaidSync, LVMError> {
+        let action = match mode {
+            ScrubMode::Check => "check",
+            ScrubMode::Repair => "repair",
+        };
+
+        // Start the scrub (has no output)
+        run_cmd::<String>(
+            "lvchange",
+            &["--syncaction", action, &self.volume.id()],
+            None::<&str>,
+        )?;
+
+        Ok(self.sync_handle())
+    }
+
+    /// Get a handle to follow the synchronization of the [RaidVolume]
+    pub fn sync_handle(&self) -> RaidSync {
+        RaidSync {
+            volume_group_name: self.volume.volume_group_name.clone(),
+            name: self.volume.name.clone(),
+        }
+    }
+}
+
+impl RaidSync {
+    /// Check the progress of the synchronization.
+    ///
+    /// Note: The [RaidVolume] must be active for its synchronization to be reported.
+    pub fn poll(&self) -> Result<RaidSyncProgress, LVMError> {
+        let raid = RaidVolume::from_id(&self.volume_group_name, &self.name)?;
+
+        match raid.sync_action {
+            Some(RaidSyncAction::Idle) => Ok(RaidSyncProgress::Complete {
+                mismatch_count: raid.mismatch_count.unwrap_or_default(),
+            }),
+            Some(action) => Ok(RaidSyncProgress::InProgress {
+                action,
+                percent: raid.sync_percent.unwrap_or_default(),
+            }),
+            None => Err(LVMError::InvalidRequest {
+                reason: format!(
+                    "{} is not active, so its synchronization cannot be followed",
+                    raid.volume.id()
+                ),
+            }),
+        }
+    }
+
+    /// Poll the synchronization every `interval` until it completes, or until
+    /// `timeout` has passed, in which case the last progress is returned.
+    pub fn wait(
+        &self,
+        interval: Duration,
+        timeout: Duration,
+    ) -> Result<RaidSyncProgress, LVMError> {
+        let start = Instant::now();
+        loop {
+            let progress = self.poll()?;
+            if let RaidSyncProgress::Complete { .. } = progress {
+                return Ok(progress);
+            }
+
+            // A frozen synchronization never completes on its own
+            if start.elapsed() + interval > timeout {
+                return Ok(progress);
+            }
+
+            thread::sleep(interval);
+        }
+    }
+}
+
+fn query(target: &str) -> Result<Vec<RaidVolume>, LVMError> {
+    let args = vec![
+        "--nolocking",
+        "--options",
+        "+lv_all",
+        "--units",
+        "b",
+        "--nosuffix",
+        "--select",
+        "segtype=~^raid",
+        "--sort",
+        "vg_name,lv_name",
+        target,
+    ];
+
+    run_cmd("lvs", &args, Some("lv"))
+}
+
+/// Deserialize a count which LVM2 reports as empty when unavailable
+fn deserialize_optional_count<'de, D>(deserializer: D) -> Result<Option<usize>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    if s.is_empty() {
+        return Ok(None);
+    }
+
+    s.parse().map(Some).map_err(serde::de::Error::custom)
+}
@@ -0,0 +1,130 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use crate::{
+    error::LVMError,
+    lv::{RaidSync, RaidVolume},
+    pv::PhysicalVolume,
+    run_cmd, ResourceUUID,
+};
+
+/// Represents a RAID image allocated on a physical volume which has failed
+#[derive(Clone, Debug)]
+pub struct FailedRaidImage {
+    /// The name of the hidden image, such as `lv_rimage_0`
+    pub image_name: String,
+
+    /// The index of the image within the [RaidVolume]
+    pub index: usize,
+
+    /// The name of the failed physical volume, usually `[unknown]` once it is missing
+    pub physical_volume_name: PathBuf,
+
+    pub physical_volume_uuid: ResourceUUID,
+}
+
+impl RaidVolume {
+    /// List the images of the [RaidVolume] which are allocated on missing physical volumes
+    pub fn failed_images(&self) -> Result<Vec<FailedRaidImage>, LVMError> {
+        let volume_group = &self.volume.volume_group_name;
+
+        let missing: HashSet<_> = PhysicalVolume::list_for_vg(volume_group)?
+            .into_iter()
+            .filter(|pv| pv.attributes.is_missing)
+            .map(|pv| pv.uuid.to_string())
+            .collect();
+
+        if missing.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut images: Vec<FailedRaidImage> = vec![];
+        for segment in PhysicalVolume::segments_for_vg(volume_group)? {
+            if segment.top_level_lv_name() != Some(self.volume.name.as_str())
+                || !missing.contains(&segment.physical_volume_uuid.to_string())
+            {
+                continue;
+            }
+
+            let Some((image_name, index)) = segment
+                .logical_volume_name
+                .as_deref()
+                .and_then(parse_image_name)
+            else {
+                continue;
+            };
+
+            // An image may span several segments of the same physical volume
+            let seen = images.iter().any(|image| {
+                image.index == index && image.physical_volume_uuid == segment.physical_volume_uuid
+            });
+
+            if !seen {
+                images.push(FailedRaidImage {
+                    image_name,
+                    index,
+                    physical_volume_name: segment.physical_volume_name,
+                    physical_volume_uuid: segment.physical_volume_uuid,
+                });
+            }
+        }
+
+        images.sort_by_key(|image| image.index);
+
+        Ok(images)
+    }
+
+    /// Replace the images on failed physical volumes, returning a handle to
+    /// follow the resynchronization of the new images.
+    ///
+    /// The new images are allocated on `target_devices`, or anywhere in the
+    /// volume group if empty.
+    pub fn repair(&self, target_devices: Vec<String>) -> Result<RaidSync, LVMError> {
+        let mut args: Vec<String> = vec!["--repair".into(), "--yes".into(), self.volume.id()];
+        args.extend(target_devices);
+
+        // Repair the volume (has no output)
+        run_cmd::<String>("lvconvert", &args, None::<&str>)?;
+
+        Self::from_id(&self.volume.volume_group_name, &self.volume.name)
+            .map(|raid| raid.sync_handle())
+    }
+
+    /// Move the images on a physical device to other physical volumes, returning
+    /// a handle to follow the resynchronization of the new images.
+    ///
+    /// The new images are allocated on `target_devices`, or anywhere in the
+    /// volume group if empty.
+    pub fn replace(&self, device: &str, target_devices: Vec<String>) -> Result<RaidSync, LVMError> {
+        if target_devices.iter().any(|target| target == device) {
+            return Err(LVMError::InvalidRequest {
+                reason: format!("cannot replace {} onto itself", device),
+            });
+        }
+
+        let mut args: Vec<String> = vec!["--replace".into(), device.into(), self.volume.id()];
+        args.extend(target_devices);
+
+        // Replace the images (has no output)
+        run_cmd::<String>("lvconvert", &args, None::<&str>)?;
+
+        Self::from_id(&self.volume.volume_group_name, &self.volume.name)
+            .map(|raid| raid.sync_handle())
+    }
+}
+
+/// Extract the image name and index from the name of a RAID sub-volume, such as
+/// `[lv_rimage_0]` or `[lv_rimage_0_iorig]`
+fn parse_image_name(name: &str) -> Option<(String, usize)> {
+    let name = name.trim_start_matches('[').trim_end_matches(']');
+
+    const SUFFIX: &str = "_rimage_";
+    let start = name.find(SUFFIX)? + SUFFIX.len();
+    let digits = name[start..]
+        .find(|c: char| !c.is_ascii_digit())
+        .map(|end| &name[start..start + end])
+        .unwrap_or(&name[start..]);
+
+    let index = digits.parse().ok()?;
+
+    Some((name[..start + digits.len()].into(), index))
+}
@@ -0,0 +1,307 @@
+use crate::{error::LVMError, ResourceCapacity};
+
+/// The layout of a [LogicalVolume](super::LogicalVolume)'s data across physical volumes
+#[derive(Clone, Debug, Default)]
+pub enum LVSegmentType {
+    /// Data is allocated sequentially
+    #[default]
+    Linear,
+
+    /// Data is striped across `stripes` physical volumes
+    Striped { stripes: usize },
+
+    /// Data is copied to `mirrors` additional images, tracked by a mirror log
+    Mirror { mirrors: usize },
+
+    /// Data is spread over images according to the RAID level. `stripes` is the
+    /// amount of data stripes (excluding parity), and `mirrors` is the amount of
+    /// additional copies for mirrored levels.
+    Raid {
+        level: RaidLevel,
+        stripes: usize,
+        mirrors: usize,
+
+        /// The amount of data written to one image before moving to the next,
+        /// which must be a power of two
+        stripe_size: Option<ResourceCapacity>,
+
+        /// The size of the regions tracked for resynchronization, which must be
+        /// a power of two
+        region_size: Option<ResourceCapacity>,
+
+        /// Whether to add dm-integrity to each image to detect and correct corruption
+        integrity: bool,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RaidLevel {
+    /// Striping without redundancy
+    Raid0,
+
+    /// Striping without redundancy, with metadata images to allow later conversion
+    Raid0Meta,
+
+    /// Mirroring
+    Raid1,
+
+    /// Striping with a dedicated parity image
+    Raid4,
+
+    /// Striping with rotating parity
+    Raid5(Raid5Layout),
+
+    /// Striping with two rotating parities
+    Raid6(Raid6Layout),
+
+    /// Striping across mirrors
+    Raid10,
+}
+
+/// The placement of parity in a [RaidLevel::Raid5] volume
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Raid5Layout {
+    LeftAsymmetric,
+    RightAsymmetric,
+    #[default]
+    LeftSymmetric,
+    RightSymmetric,
+
+    /// Parity is kept on the last image, like [RaidLevel::Raid4]
+    Dedicated,
+}
+
+/// The placement of parity in a [RaidLevel::Raid6] volume
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Raid6Layout {
+    #[default]
+    ZeroRestart,
+    NRestart,
+    NContinue,
+
+    /// Both parities are kept on the last two images
+    Dedicated,
+
+    /// Like [Raid5Layout::LeftAsymmetric], with the second parity kept on the last
+    /// image, as used when converting from [RaidLevel::Raid5]
+    LeftAsymmetric6,
+
+    /// Like [Raid5Layout::RightAsymmetric], with the second parity kept on the last image
+    RightAsymmetric6,
+
+    /// Like [Raid5Layout::LeftSymmetric], with the second parity kept on the last image
+    LeftSymmetric6,
+
+    /// Like [Raid5Layout::RightSymmetric], with the second parity kept on the last image
+    RightSymmetric6,
+}
+
+impl RaidLevel {
+    pub(crate) fn as_arg(&self) -> &'static str {
+        match self {
+            RaidLevel::Raid0 => "raid0",
+            RaidLevel::Raid0Meta => "raid0_meta",
+            RaidLevel::Raid1 => "raid1",
+            RaidLevel::Raid4 => "raid4",
+            RaidLevel::Raid5(layout) => match layout {
+                Raid5Layout::LeftAsymmetric => "raid5_la",
+                Raid5Layout::RightAsymmetric => "raid5_ra",
+                Raid5Layout::LeftSymmetric => "raid5_ls",
+                Raid5Layout::RightSymmetric => "raid5_rs",
+                Raid5Layout::Dedicated => "raid5_n",
+            },
+            RaidLevel::Raid6(layout) => match layout {
+                Raid6Layout::ZeroRestart => "raid6_zr",
+                Raid6Layout::NRestart => "raid6_nr",
+                Raid6Layout::NContinue => "raid6_nc",
+                Raid6Layout::Dedicated => "raid6_n_6",
+                Raid6Layout::LeftAsymmetric6 => "raid6_la_6",
+                Raid6Layout::RightAsymmetric6 => "raid6_ra_6",
+                Raid6Layout::LeftSymmetric6 => "raid6_ls_6",
+                Raid6Layout::RightSymmetric6 => "raid6_rs_6",
+            },
+            RaidLevel::Raid10 => "raid10",
+        }
+    }
+
+    /// The amount of parity images kept per stripe
+    fn parity_images(&self) -> usize {
+        match self {
+            RaidLevel::Raid4 | RaidLevel::Raid5(_) => 1,
+            RaidLevel::Raid6(_) => 2,
+            _ => 0,
+        }
+    }
+
+    /// The minimum amount of data stripes supported by the level
+    fn min_stripes(&self) -> usize {
+        match self {
+            RaidLevel::Raid1 => 1,
+            RaidLevel::Raid6(_) => 3,
+            _ => 2,
+        }
+    }
+
+    fn is_mirrored(&self) -> bool {
+        matches!(self, RaidLevel::Raid1 | RaidLevel::Raid10)
+    }
+}
+
+impl LVSegmentType {
+    /// Convert the segment type into arguments for `lvcreate`
+    pub(crate) fn to_args(&self) -> Vec<String> {
+        match self {
+            LVSegmentType::Linear => vec!["--type".into(), "linear".into()],
+            LVSegmentType::Striped { stripes } => vec![
+                "--type".into(),
+                "striped".into(),
+                "--stripes".into(),
+                stripes.to_string(),
+            ],
+            LVSegmentType::Mirror { mirrors } => vec![
+                "--type".into(),
+                "mirror".into(),
+                "--mirrors".into(),
+                mirrors.to_string(),
+            ],
+            LVSegmentType::Raid {
+                level,
+                stripes,
+                mirrors,
+                stripe_size,
+                region_size,
+                integrity,
+            } => {
+                let mut args = vec!["--type".into(), level.as_arg().into()];
+                if *level != RaidLevel::Raid1 {
+                    args.extend(["--stripes".into(), stripes.to_string()]);
+                }
+
+                if level.is_mirrored() {
+                    args.extend(["--mirrors".into(), mirrors.to_string()]);
+                }
+
+                if let Some(stripe_size) = stripe_size {
+                    args.extend(["--stripesize".into(), format!("{}B", **stripe_size)]);
+                }
+
+                if let Some(region_size) = region_size {
+                    args.extend(["--regionsize".into(), format!("{}B", **region_size)]);
+                }
+
+                if *integrity {
+                    args.extend(["--raidintegrity".into(), "y".into()]);
+                }
+
+                args
+            }
+        }
+    }
+
+    /// Check that the layout is supported by LVM2
+    pub fn validate(&self) -> Result<(), LVMError> {
+        let invalid = |reason: String| Err(LVMError::InvalidRequest { reason });
+
+        match self {
+            LVSegmentType::Linear => Ok(()),
+            LVSegmentType::Striped { stripes } if *stripes < 1 => {
+                invalid("striped volumes require at least one stripe".into())
+            }
+            LVSegmentType::Mirror { mirrors } if *mirrors < 1 => {
+                invalid("mirrored volumes require at least one mirror".into())
+            }
+            LVSegmentType::Striped { .. } | LVSegmentType::Mirror { .. } => Ok(()),
+            LVSegmentType::Raid {
+                level,
+                stripes,
+                mirrors,
+                stripe_size,
+                region_size,
+                integrity,
+            } => {
+                if *level != RaidLevel::Raid1 && *stripes < level.min_stripes() {
+                    return invalid(format!(
+                        "{} requires at least {} stripes, got {}",
+                        level.as_arg(),
+                        level.min_stripes(),
+                        stripes
+                    ));
+                }
+
+                if level.is_mirrored() && *mirrors < 1 {
+                    return invalid(format!("{} requires at least one mirror", level.as_arg()));
+                }
+
+                for (name, size) in [("stripe", stripe_size), ("region", region_size)] {
+                    if let Some(size) = size {
+                        if !size.is_power_of_two() {
+                            return invalid(format!(
+                                "{} size must be a power of two, got {}B",
+                                name, **size
+                            ));
+                        }
+                    }
+                }
+
+                if *integrity && matches!(level, RaidLevel::Raid0 | RaidLevel::Raid0Meta) {
+                    return invalid(format!(
+                        "{} has no redundancy to support integrity",
+                        level.as_arg()
+                    ));
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    /// The amount of stripes the data is split into
+    pub fn data_stripes(&self) -> usize {
+        match self {
+            LVSegmentType::Linear | LVSegmentType::Mirror { .. } => 1,
+            LVSegmentType::Striped { stripes } => *stripes,
+            LVSegmentType::Raid { level, stripes, .. } => match level {
+                RaidLevel::Raid1 => 1,
+                _ => *stripes,
+            },
+        }
+    }
+
+    /// The amount of data images, each of which is allocated on a separate
+    /// physical volume
+    pub fn images(&self) -> usize {
+        match self {
+            LVSegmentType::Linear => 1,
+            LVSegmentType::Striped { stripes } => *stripes,
+            LVSegmentType::Mirror { mirrors } => mirrors + 1,
+            LVSegmentType::Raid {
+                level,
+                stripes,
+                mirrors,
+                ..
+            } => match level {
+                RaidLevel::Raid0 | RaidLevel::Raid0Meta => *stripes,
+                RaidLevel::Raid1 => mirrors + 1,
+                RaidLevel::Raid4 | RaidLevel::Raid5(_) | RaidLevel::Raid6(_) => {
+                    stripes + level.parity_images()
+                }
+                RaidLevel::Raid10 => stripes * (mirrors + 1),
+            },
+        }
+    }
+
+    /// The amount of extra extents needed for metadata, such as mirror logs and
+    /// RAID metadata images
+    pub fn metadata_extents(&self) -> usize {
+        match self {
+            LVSegmentType::Linear | LVSegmentType::Striped { .. } => 0,
+            // A single disk log
+            LVSegmentType::Mirror { .. } => 1,
+            LVSegmentType::Raid { level, .. } => match level {
+                RaidLevel::Raid0 => 0,
+                // One metadata extent per image
+                _ => self.images(),
+            },
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use crate::{error::LVMError, lv::LogicalVolume, run_cmd, ResourceCapacity, ResourceName};
+
+impl LogicalVolume {
+    /// Create a copy-on-write snapshot of the [LogicalVolume].
+    ///
+    /// The snapshot is allocated `cow_size` bytes to hold changes made to either
+    /// volume, and becomes invalid once that space is used up.
+    pub fn snapshot(
+        &self,
+        name: ResourceName,
+        cow_size: ResourceCapacity,
+    ) -> Result<LogicalVolume, LVMError> {
+        let byte_str = format!("{}B", *cow_size);
+        let args = [
+            "--snapshot",
+            "--name",
+            name.as_str(),
+            "--size",
+            &byte_str,
+            &self.id(),
+        ];
+
+        // Create the snapshot (has no output)
+        run_cmd::<String>("lvcreate", &args, None::<&str>)?;
+
+        Self::from_id(&self.volume_group_name, &name)
+    }
+
+    /// List all snapshots of the [LogicalVolume]
+    pub fn snapshots(&self) -> Result<Vec<LogicalVolume>, LVMError> {
+        let lvs = LogicalVolume::list_for_vg(&self.volume_group_name)?;
+
+        Ok(lvs
+            .into_iter()
+            .filter(|lv| lv.snapshot_origin().as_ref() == Some(&self.name))
+            .collect())
+    }
+}
@@ -0,0 +1,259 @@
+use serde::{Deserialize, Deserializer};
+use serde_aux::field_attributes::deserialize_number_from_string;
+
+use crate::{
+    deserialize_flag, deserialize_percent, error::LVMError, lv::LogicalVolume, run_cmd,
+    ResourceCapacity, ResourceName,
+};
+
+/// The smallest chunk size supported by thin pools
+const MIN_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The largest chunk size supported by thin pools
+const MAX_CHUNK_SIZE: usize = 1024 * 1024 * 1024;
+
+/// Represents a thin pool, which provides space on demand to thin volumes
+#[derive(Clone, Debug, Deserialize)]
+pub struct ThinPool {
+    #[serde(flatten)]
+    pub volume: LogicalVolume,
+
+    /// The percentage of the data space in use
+    #[serde(deserialize_with = "deserialize_percent")]
+    pub data_percent: Option<f64>,
+
+    /// The percentage of the metadata space in use
+    #[serde(deserialize_with = "deserialize_percent")]
+    pub metadata_percent: Option<f64>,
+
+    /// The size of the unit in which data is allocated
+    #[serde(
+        rename = "chunk_size",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub chunk_size_bytes: usize,
+
+    /// Iff newly allocated chunks are zeroed before use
+    #[serde(rename = "zero", deserialize_with = "deserialize_flag")]
+    pub is_zeroing: bool,
+
+    pub discards: ThinDiscards,
+
+    /// Iff writes fail immediately once the pool is full, rather than being queued
+    #[serde(rename = "lv_when_full", deserialize_with = "deserialize_when_full")]
+    pub error_when_full: bool,
+
+    /// Iff the pool is monitored by dmeventd, which extends it automatically
+    #[serde(rename = "seg_monitor", deserialize_with = "deserialize_monitor")]
+    pub is_monitored: bool,
+}
+
+/// How discards issued to thin volumes are handled by a [ThinPool]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ThinDiscards {
+    /// Discards are ignored
+    Ignore,
+
+    /// Discards free chunks in the pool, but are not passed to the underlying devices
+    NoPassdown,
+
+    /// Discards free chunks in the pool and are passed to the underlying devices
+    Passdown,
+}
+
+impl ThinDiscards {
+    pub(crate) fn as_arg(&self) -> &'static str {
+        match self {
+            ThinDiscards::Ignore => "ignore",
+            ThinDiscards::NoPassdown => "nopassdown",
+            ThinDiscards::Passdown => "passdown",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ThinPoolCreateOptions {
+    /// Name for the new pool
+    pub name: ResourceName,
+
+    /// The capacity, in bytes, of the data space
+    pub data_size: ResourceCapacity,
+
+    /// The capacity, in bytes, of the metadata space, defaulting to one sized
+    /// for the data space by LVM2
+    pub metadata_size: Option<ResourceCapacity>,
+
+    /// The size of the unit in which data is allocated, which must be a multiple
+    /// of 64KiB between 64KiB and 1GiB
+    pub chunk_size: Option<ResourceCapacity>,
+
+    /// Whether newly allocated chunks are zeroed before use
+    pub zero: Option<bool>,
+
+    pub discards: Option<ThinDiscards>,
+
+    /// Whether writes fail immediately once the pool is full, rather than being queued
+    pub error_when_full: Option<bool>,
+
+    /// Whether the pool is monitored by dmeventd
+    pub monitor: Option<bool>,
+
+    /// Optional extra tags to append
+    pub tags: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ThinPoolChangeOptions {
+    pub zero: Option<bool>,
+    pub discards: Option<ThinDiscards>,
+    pub error_when_full: Option<bool>,
+    pub monitor: Option<bool>,
+}
+
+impl ThinPool {
+    /// Create a [ThinPool] in a volume group
+    pub fn create(
+        volume_group: &ResourceName,
+        opts: ThinPoolCreateOptions,
+    ) -> Result<ThinPool, LVMError> {
+        let mut args: Vec<String> = vec![
+            "--type".into(),
+            "thin-pool".into(),
+            "--name".into(),
+            opts.name.to_string(),
+            "--size".into(),
+            format!("{}B", *opts.data_size),
+        ];
+
+        if let Some(metadata_size) = opts.metadata_size {
+            args.extend(["--poolmetadatasize".into(), format!("{}B", *metadata_size)]);
+        }
+
+        if let Some(chunk_size) = opts.chunk_size {
+            if *chunk_size < MIN_CHUNK_SIZE
+                || *chunk_size > MAX_CHUNK_SIZE
+                || *chunk_size % MIN_CHUNK_SIZE != 0
+            {
+                return Err(LVMError::InvalidRequest {
+                    reason: format!(
+                        "thin pool chunk size must be a multiple of 64KiB between 64KiB and 1GiB, got {}B",
+                        *chunk_size
+                    ),
+                });
+            }
+
+            args.extend(["--chunksize".into(), format!("{}B", *chunk_size)]);
+        }
+
+        args.extend(to_args(
+            opts.zero,
+            opts.discards,
+            opts.error_when_full,
+            opts.monitor,
+        ));
+        args.extend(opts.tags.into_iter().flat_map(|t| ["--addtag".into(), t]));
+        args.push(volume_group.to_string());
+
+        // Create the pool (has no output)
+        run_cmd::<String>("lvcreate", &args, None::<&str>)?;
+
+        Self::from_id(volume_group, &opts.name)
+    }
+
+    /// Get a [ThinPool] from its ID pair (volume group / name)
+    pub fn from_id(volume_group: &ResourceName, name: &ResourceName) -> Result<ThinPool, LVMError> {
+        let id = format!("{}/{}", volume_group, name);
+
+        Self::query(&id)?
+            .pop()
+            .ok_or(LVMError::NotFound { resource: id })
+    }
+
+    /// List all [ThinPool]s of a volume group
+    pub fn list_for_vg(volume_group: &ResourceName) -> Result<Vec<ThinPool>, LVMError> {
+        Self::query(volume_group)
+    }
+
+    /// Change the configuration of the [ThinPool], refreshing its state afterwards.
+    pub fn change(&mut self, opts: ThinPoolChangeOptions) -> Result<(), LVMError> {
+        let mut args = to_args(opts.zero, opts.discards, opts.error_when_full, opts.monitor);
+        if args.is_empty() {
+            return Ok(());
+        }
+
+        args.push(self.volume.id());
+
+        // Change the pool (has no output)
+        run_cmd::<String>("lvchange", &args, None::<&str>)?;
+
+        *self = Self::from_id(&self.volume.volume_group_name, &self.volume.name)?;
+
+        Ok(())
+    }
+
+    fn query(target: &str) -> Result<Vec<ThinPool>, LVMError> {
+        let args = vec![
+            "--nolocking",
+            "--options",
+            "+lv_all,chunk_size,zero,discards,seg_monitor",
+            "--units",
+            "b",
+            "--nosuffix",
+            "--select",
+            "segtype=thin-pool",
+            "--sort",
+            "vg_name,lv_name",
+            target,
+        ];
+
+        run_cmd("lvs", &args, Some("lv"))
+    }
+}
+
+/// Convert the settings shared by creating and changing a [ThinPool] into arguments
+fn to_args(
+    zero: Option<bool>,
+    discards: Option<ThinDiscards>,
+    error_when_full: Option<bool>,
+    monitor: Option<bool>,
+) -> Vec<String> {
+    let yes_no = |b: bool| if b { "y" } else { "n" }.to_string();
+
+    let mut args: Vec<String> = vec![];
+    if let Some(zero) = zero {
+        args.extend(["--zero".into(), yes_no(zero)]);
+    }
+
+    if let Some(discards) = discards {
+        args.extend(["--discards".into(), discards.as_arg().into()]);
+    }
+
+    if let Some(error_when_full) = error_when_full {
+        args.extend(["--errorwhenfull".into(), yes_no(error_when_full)]);
+    }
+
+    if let Some(monitor) = monitor {
+        args.extend(["--monitor".into(), yes_no(monitor)]);
+    }
+
+    args
+}
+
+fn deserialize_when_full<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+
+    Ok(s == "error")
+}
+
+fn deserialize_monitor<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+
+    Ok(s == "monitored")
+}
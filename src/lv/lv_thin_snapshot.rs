@@ -0,0 +1,138 @@
+use crate::{
+    error::LVMError,
+    lv::{LogicalVolume, ThinPool, ThinVolume},
+    run_cmd, Permissions, ResourceName,
+};
+
+#[derive(Clone, Debug, Default)]
+pub struct ThinSnapshotOptions {
+    /// Whether the snapshot should become active after creation, regardless of
+    /// whether it skips normal activation
+    pub activate: bool,
+
+    /// Whether the snapshot is skipped during normal activation, defaulting to
+    /// LVM2's configuration (usually skipped)
+    pub skip_activation: Option<bool>,
+
+    /// Optional extra tags to append
+    pub tags: Vec<String>,
+}
+
+impl ThinSnapshotOptions {
+    fn to_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = vec![];
+        if let Some(skip) = self.skip_activation {
+            args.extend([
+                "--setactivationskip".into(),
+                if skip { "y" } else { "n" }.into(),
+            ]);
+        }
+
+        if self.activate {
+            args.extend([
+                "--activate".into(),
+                "y".into(),
+                "--ignoreactivationskip".into(),
+            ]);
+        } else {
+            args.extend(["--activate".into(), "n".into()]);
+        }
+
+        args.extend(
+            self.tags
+                .iter()
+                .flat_map(|t| ["--addtag".into(), t.clone()]),
+        );
+
+        args
+    }
+}
+
+impl ThinVolume {
+    /// Create a thin snapshot of the [ThinVolume], sharing its [ThinPool]
+    pub fn snapshot(
+        &self,
+        name: ResourceName,
+        opts: ThinSnapshotOptions,
+    ) -> Result<ThinVolume, LVMError> {
+        let mut args: Vec<String> = vec!["--snapshot".into(), "--name".into(), name.to_string()];
+        args.extend(opts.to_args());
+        args.push(self.volume.id());
+
+        // Create the snapshot (has no output)
+        run_cmd::<String>("lvcreate", &args, None::<&str>)?;
+
+        ThinVolume::from_id(&self.volume.volume_group_name, &name)
+    }
+}
+
+impl ThinPool {
+    /// Create a thin snapshot of a read-only volume outside of the [ThinPool],
+    /// which is used as an external origin. Unchanged data is read from the
+    /// origin, while changes are stored in the [ThinPool].
+    pub fn snapshot_external(
+        &self,
+        origin: &LogicalVolume,
+        name: ResourceName,
+        opts: ThinSnapshotOptions,
+    ) -> Result<ThinVolume, LVMError> {
+        if origin.attributes.permissions != Permissions::ReadOnly {
+            return Err(LVMError::InvalidRequest {
+                reason: format!("external origin {} must be read-only", origin.id()),
+            });
+        }
+
+        let mut args: Vec<String> = vec![
+            "--snapshot".into(),
+            "--name".into(),
+            name.to_string(),
+            "--thinpool".into(),
+            self.volume.id(),
+        ];
+        args.extend(opts.to_args());
+        args.push(origin.id());
+
+        // Create the snapshot (has no output)
+        run_cmd::<String>("lvcreate", &args, None::<&str>)?;
+
+        ThinVolume::from_id(&self.volume.volume_group_name, &name)
+    }
+}
+
+impl LogicalVolume {
+    /// Get the origin of the [LogicalVolume], if it is a snapshot
+    pub fn origin_volume(&self) -> Result<Option<LogicalVolume>, LVMError> {
+        self.snapshot_origin()
+            .map(|origin| LogicalVolume::from_id(&self.volume_group_name, &origin))
+            .transpose()
+    }
+
+    /// Set whether the [LogicalVolume] is skipped during normal activation,
+    /// refreshing its state afterwards.
+    pub fn set_skip_activation(&mut self, skip: bool) -> Result<(), LVMError> {
+        run_cmd::<String>(
+            "lvchange",
+            &[
+                "--setactivationskip",
+                if skip { "y" } else { "n" },
+                &self.id(),
+            ],
+            None::<&str>,
+        )?;
+
+        *self = Self::from_id(&self.volume_group_name, &self.name)?;
+
+        Ok(())
+    }
+
+    /// Activate the [LogicalVolume], even if it is skipped during normal activation
+    pub fn activate_ignoring_skip(&mut self) -> Result<(), LVMError> {
+        run_cmd::<String>(
+            "lvchange",
+            &["--activate", "y", "--ignoreactivationskip", &self.id()],
+            None::<&str>,
+        )?;
+
+        Ok(())
+    }
+}
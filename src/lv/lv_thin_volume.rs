@@ -0,0 +1,142 @@
+use serde::Deserialize;
+use serde_aux::field_attributes::deserialize_number_from_string;
+
+use crate::{
+    deserialize_percent,
+    error::LVMError,
+    lv::{LogicalVolume, ThinPool},
+    run_cmd, ResourceCapacity, ResourceName,
+};
+
+/// Represents a thin volume, whose space is provided on demand by a [ThinPool]
+#[derive(Clone, Debug, Deserialize)]
+pub struct ThinVolume {
+    /// The volume itself, whose capacity is its virtual size
+    #[serde(flatten)]
+    pub volume: LogicalVolume,
+
+    /// The name of the [ThinPool] providing the volume's space
+    #[serde(rename = "pool_lv")]
+    pub pool_name: ResourceName,
+
+    /// The ID of the volume within its [ThinPool]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub thin_id: usize,
+
+    /// The percentage of the virtual size which has been allocated in the pool
+    #[serde(deserialize_with = "deserialize_percent")]
+    pub data_percent: Option<f64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ThinVolumeCreateOptions {
+    /// Whether the thin volume should become active after creation
+    pub activate: bool,
+
+    /// The capacity, in bytes, presented by the volume
+    pub virtual_size: ResourceCapacity,
+
+    /// Name for the new volume
+    pub name: ResourceName,
+
+    /// Optional extra tags to append
+    pub tags: Vec<String>,
+}
+
+/// The overprovisioning of a [ThinPool] by its [ThinVolume]s
+#[derive(Clone, Debug)]
+pub struct ThinProvisioning {
+    /// The size of the pool's data space
+    pub pool_size_bytes: usize,
+
+    /// The sum of the virtual sizes of the pool's thin volumes
+    pub virtual_size_bytes: usize,
+
+    /// The virtual size divided by the pool size, where anything above 1 is overprovisioned
+    pub ratio: f64,
+
+    pub thin_volume_count: usize,
+}
+
+impl ThinVolume {
+    /// Get a [ThinVolume] from its ID pair (volume group / name)
+    pub fn from_id(
+        volume_group: &ResourceName,
+        name: &ResourceName,
+    ) -> Result<ThinVolume, LVMError> {
+        let id = format!("{}/{}", volume_group, name);
+
+        query(&id, "segtype=thin")?
+            .pop()
+            .ok_or(LVMError::NotFound { resource: id })
+    }
+}
+
+impl ThinPool {
+    /// Create a [ThinVolume] provisioned from the [ThinPool]
+    pub fn create_thin_volume(
+        &self,
+        opts: ThinVolumeCreateOptions,
+    ) -> Result<ThinVolume, LVMError> {
+        let mut args: Vec<String> = vec![
+            "--type".into(),
+            "thin".into(),
+            "--activate".into(),
+            if opts.activate { "ay" } else { "an" }.into(),
+            "--name".into(),
+            opts.name.to_string(),
+            "--virtualsize".into(),
+            format!("{}B", *opts.virtual_size),
+            "--thinpool".into(),
+            self.volume.name.to_string(),
+        ];
+
+        args.extend(opts.tags.into_iter().flat_map(|t| ["--addtag".into(), t]));
+        args.push(self.volume.volume_group_name.to_string());
+
+        // Create the volume (has no output)
+        run_cmd::<String>("lvcreate", &args, None::<&str>)?;
+
+        ThinVolume::from_id(&self.volume.volume_group_name, &opts.name)
+    }
+
+    /// List all [ThinVolume]s provisioned from the [ThinPool]
+    pub fn thin_volumes(&self) -> Result<Vec<ThinVolume>, LVMError> {
+        let selector = format!("segtype=thin && pool_lv={}", self.volume.name);
+
+        query(&self.volume.volume_group_name, &selector)
+    }
+
+    /// Report how far the [ThinPool] is overprovisioned by its [ThinVolume]s
+    pub fn provisioning(&self) -> Result<ThinProvisioning, LVMError> {
+        let volumes = self.thin_volumes()?;
+
+        let pool_size_bytes = *self.volume.capacity_bytes;
+        let virtual_size_bytes = volumes.iter().map(|tv| *tv.volume.capacity_bytes).sum();
+
+        Ok(ThinProvisioning {
+            pool_size_bytes,
+            virtual_size_bytes,
+            ratio: virtual_size_bytes as f64 / pool_size_bytes as f64,
+            thin_volume_count: volumes.len(),
+        })
+    }
+}
+
+fn query(target: &str, selector: &str) -> Result<Vec<ThinVolume>, LVMError> {
+    let args = vec![
+        "--nolocking",
+        "--options",
+        "+lv_all,thin_id",
+        "--units",
+        "b",
+        "--nosuffix",
+        "--select",
+        selector,
+        "--sort",
+        "vg_name,lv_name",
+        target,
+    ];
+
+    run_cmd("lvs", &args, Some("lv"))
+}
@@ -40,8 +40,8 @@ impl TryFromChar for LVVolumeType {
             'R' => LVVolumeType::Raid {
                 initial_sync: false,
             },
-            's' => LVVolumeType::Snapshot { merging: true },
-            'S' => LVVolumeType::Snapshot { merging: false },
+            's' => LVVolumeType::Snapshot { merging: false },
+            'S' => LVVolumeType::Snapshot { merging: true },
             'p' => LVVolumeType::PVMove,
             'v' => LVVolumeType::Virtual,
             'i' => LVVolumeType::MirrorOrRaid { out_of_sync: false },
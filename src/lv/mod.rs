@@ -1,11 +1,28 @@
 mod lv_attributes;
+mod lv_merge;
+mod lv_raid;
+mod lv_raid_repair;
+mod lv_resize;
+mod lv_segment_type;
+mod lv_snapshot;
 mod lv_state;
 mod lv_status;
+mod lv_thin_pool;
+mod lv_thin_snapshot;
+mod lv_thin_volume;
 mod lv_volume_type;
 
 pub use lv_attributes::*;
+pub use lv_merge::*;
+pub use lv_raid::*;
+pub use lv_raid_repair::*;
+pub use lv_resize::*;
+pub use lv_segment_type::*;
 pub use lv_state::*;
 pub use lv_status::*;
+pub use lv_thin_pool::*;
+pub use lv_thin_snapshot::*;
+pub use lv_thin_volume::*;
 pub use lv_volume_type::*;
 
 use std::path::PathBuf;
@@ -13,7 +30,8 @@ use std::path::PathBuf;
 use serde::Deserialize;
 
 use crate::{
-    error::LVMError, run_cmd, ResourceCapacity, ResourceName, ResourceSelector, ResourceUUID,
+    deserialize_flag, deserialize_optional, deserialize_percent, error::LVMError, run_cmd,
+    ResourceCapacity, ResourceName, ResourceSelector, ResourceUUID,
 };
 
 #[derive(Clone, Debug, Deserialize)]
@@ -36,6 +54,23 @@ pub struct LogicalVolume {
 
     #[serde(rename = "lv_uuid")]
     pub uuid: ResourceUUID,
+
+    /// The name of the origin volume, if this is a snapshot. Cached volumes report
+    /// their hidden origin in brackets, such as `[data_corig]`.
+    #[serde(deserialize_with = "deserialize_optional")]
+    pub origin: Option<String>,
+
+    /// The UUID of the origin volume, if this is a snapshot
+    #[serde(deserialize_with = "deserialize_optional")]
+    pub origin_uuid: Option<ResourceUUID>,
+
+    /// The percentage of the snapshot's copy-on-write space in use, if this is a snapshot
+    #[serde(rename = "snap_percent", deserialize_with = "deserialize_percent")]
+    pub snapshot_percent: Option<f64>,
+
+    /// Iff the volume is skipped during normal activation, as thin snapshots are by default
+    #[serde(rename = "lv_skip_activation", deserialize_with = "deserialize_flag")]
+    pub skip_activation: bool,
 }
 
 impl LogicalVolume {
@@ -44,6 +79,8 @@ impl LogicalVolume {
         volume_group: &ResourceName,
         opts: LVCreateOptions,
     ) -> Result<LogicalVolume, LVMError> {
+        opts.segment_type.validate()?;
+
         let tags: Vec<String> = opts
             .tags
             .into_iter()
@@ -62,12 +99,17 @@ impl LogicalVolume {
             &byte_str,
         ];
 
-        let mut args = [args.into_iter().map(|s| s.into()).collect(), tags].concat();
+        let mut args = [
+            args.into_iter().map(|s| s.into()).collect(),
+            opts.segment_type.to_args(),
+            tags,
+        ]
+        .concat();
         args.push(volume_group.to_string());
 
         // Create the volume (has no output)
         run_cmd::<String>("lvcreate", &args, None::<&str>)
-            .and_then(|_| Self::from_id(&volume_group, &opts.name))
+            .and_then(|_| Self::from_id(volume_group, &opts.name))
     }
 
     /// Get a [LogicalVolume] from its ID pair (volume group / name)
@@ -123,6 +165,45 @@ impl LogicalVolume {
         Ok(())
     }
 
+    /// Rename the [LogicalVolume], refreshing its state afterwards.
+    pub fn rename(&mut self, new_name: ResourceName) -> Result<(), LVMError> {
+        let taken = LogicalVolume::list_for_vg(&self.volume_group_name)?
+            .iter()
+            .any(|lv| lv.name == new_name);
+
+        if taken {
+            return Err(LVMError::InvalidRequest {
+                reason: format!(
+                    "logical volume {}/{} already exists",
+                    self.volume_group_name, new_name
+                ),
+            });
+        }
+
+        // Rename the volume (has no output)
+        run_cmd::<String>(
+            "lvrename",
+            &[
+                self.volume_group_name.as_str(),
+                self.name.as_str(),
+                new_name.as_str(),
+            ],
+            None::<&str>,
+        )?;
+
+        *self = Self::from_id(&self.volume_group_name, &new_name)?;
+
+        Ok(())
+    }
+
+    /// The name of the origin volume, if this is a snapshot
+    pub fn snapshot_origin(&self) -> Option<ResourceName> {
+        // Hidden origins of cached volumes are not valid names
+        self.origin
+            .as_deref()
+            .and_then(|origin| ResourceName::try_from(origin).ok())
+    }
+
     pub fn id(&self) -> String {
         format!("{}/{}", self.volume_group_name, self.name)
     }
@@ -137,7 +218,27 @@ impl LogicalVolume {
     pub fn set_activated(&mut self, should_activate: bool) -> Result<(), LVMError> {
         run_cmd::<String>(
             "lvchange",
-            &["--activate", if should_activate { "ay" } else { "n" }],
+            &[
+                "--activate",
+                if should_activate { "ay" } else { "n" },
+                &self.id(),
+            ],
+            None::<&str>,
+        )?;
+
+        Ok(())
+    }
+
+    /// Activate the [LogicalVolume] in a shared volume group, holding the specified lock
+    pub fn activate_with_lock(&mut self, mode: LVLockMode) -> Result<(), LVMError> {
+        let activation = match mode {
+            LVLockMode::Exclusive => "ey",
+            LVLockMode::Shared => "sy",
+        };
+
+        run_cmd::<String>(
+            "lvchange",
+            &["--activate", activation, &self.id()],
             None::<&str>,
         )?;
 
@@ -170,7 +271,7 @@ impl ResourceSelector for LogicalVolume {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct LVCreateOptions {
     /// Whether the logical volume should become active after creation
     pub activate: bool,
@@ -181,6 +282,69 @@ pub struct LVCreateOptions {
     /// Name for the new volume
     pub name: ResourceName,
 
+    /// The layout of the volume across physical volumes, linear by default
+    pub segment_type: LVSegmentType,
+
     /// Optional extra tags to append
     pub tags: Vec<String>,
 }
+
+/// The lock held by a host when activating a [LogicalVolume] in a shared volume group
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LVLockMode {
+    /// Only this host may activate the [LogicalVolume]
+    Exclusive,
+
+    /// Other hosts may activate the [LogicalVolume] at the same time
+    Shared,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_cached_volume() {
+        // A record from `lvs --reportformat json --options +lv_all`, trimmed to
+        // the reported fields
+        let record = r#"{
+            "lv_name": "data",
+            "lv_size": "10737418240",
+            "vg_name": "vg0",
+            "lv_attr": "Cwi-a-C---",
+            "lv_path": "/dev/vg0/data",
+            "lv_uuid": "Aa1Bb2-Cc3D-d4Ee-5Ff6-Gg7H-h8Ii-9Jj0Kk",
+            "origin": "[data_corig]",
+            "origin_uuid": "Zz9Yy8-Xx7W-w6Vv-5Uu4-Tt3S-s2Rr-1Qq0Pp",
+            "snap_percent": "",
+            "lv_skip_activation": ""
+        }"#;
+
+        let lv: LogicalVolume = serde_json::from_str(record).unwrap();
+
+        assert!(matches!(lv.attributes.volume_type, LVVolumeType::Cache));
+        assert_eq!(lv.origin.as_deref(), Some("[data_corig]"));
+        assert_eq!(lv.snapshot_origin(), None);
+    }
+
+    #[test]
+    fn deserializes_snapshot() {
+        let record = r#"{
+            "lv_name": "data_snap",
+            "lv_size": "1073741824",
+            "vg_name": "vg0",
+            "lv_attr": "swi-a-s---",
+            "lv_path": "/dev/vg0/data_snap",
+            "lv_uuid": "Q1w2E3-r4T5-y6U7-i8O9-p0As-dFgh-JkLzXc",
+            "origin": "data",
+            "origin_uuid": "Aa1Bb2-Cc3D-d4Ee-5Ff6-Gg7H-h8Ii-9Jj0Kk",
+            "snap_percent": "12.50",
+            "lv_skip_activation": ""
+        }"#;
+
+        let lv: LogicalVolume = serde_json::from_str(record).unwrap();
+
+        assert_eq!(lv.snapshot_origin(), Some("data".try_into().unwrap()));
+        assert_eq!(lv.snapshot_percent, Some(12.5));
+    }
+}
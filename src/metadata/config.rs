@@ -0,0 +1,324 @@
+use std::{error::Error, fmt::Display, iter::Peekable, str::Chars};
+
+/// Represents a single value of the LVM2 text metadata format
+#[derive(Clone, Debug, PartialEq)]
+pub enum MetadataValue {
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<MetadataValue>),
+}
+
+impl Display for MetadataValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(i) => write!(f, "{}", i),
+            Self::Float(x) => write!(f, "{:?}", x),
+            Self::String(s) => {
+                write!(f, "\"")?;
+                for c in s.chars() {
+                    if c == '"' || c == '\\' {
+                        write!(f, "\\")?;
+                    }
+
+                    write!(f, "{}", c)?;
+                }
+
+                write!(f, "\"")
+            }
+            Self::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    write!(f, "{}", value)?;
+                }
+
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+/// Represents an error in parsing LVM2 text metadata
+#[derive(Debug)]
+pub struct InvalidMetadataError(pub(crate) String);
+impl Error for InvalidMetadataError {}
+
+impl Display for InvalidMetadataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "metadata must be valid LVM2 text format: {}", self.0)
+    }
+}
+
+/// A node in the tree of a parsed config
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum ConfigNode {
+    Value(MetadataValue),
+    Section(ConfigSection),
+}
+
+/// A section of a config, holding its entries in their original order
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct ConfigSection {
+    pub(crate) entries: Vec<(String, ConfigNode)>,
+}
+
+impl ConfigSection {
+    /// Get the value with the specified key, if present
+    pub(crate) fn value(&self, key: &str) -> Option<&MetadataValue> {
+        self.entries.iter().find_map(|(k, node)| match node {
+            ConfigNode::Value(value) if k == key => Some(value),
+            _ => None,
+        })
+    }
+
+    /// Get the subsection with the specified key, if present
+    pub(crate) fn section(&self, key: &str) -> Option<&ConfigSection> {
+        self.sections()
+            .find_map(|(k, section)| (k == key).then_some(section))
+    }
+
+    /// Iterate over all subsections along with their keys
+    pub(crate) fn sections(&self) -> impl Iterator<Item = (&str, &ConfigSection)> {
+        self.entries.iter().filter_map(|(k, node)| match node {
+            ConfigNode::Section(section) => Some((k.as_str(), section)),
+            _ => None,
+        })
+    }
+
+    /// Iterate over all values along with their keys
+    pub(crate) fn values(&self) -> impl Iterator<Item = (&str, &MetadataValue)> {
+        self.entries.iter().filter_map(|(k, node)| match node {
+            ConfigNode::Value(value) => Some((k.as_str(), value)),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn push_value(&mut self, key: impl Into<String>, value: MetadataValue) {
+        self.entries.push((key.into(), ConfigNode::Value(value)));
+    }
+
+    pub(crate) fn push_section(&mut self, key: impl Into<String>, section: ConfigSection) {
+        self.entries
+            .push((key.into(), ConfigNode::Section(section)));
+    }
+
+    /// Write the section's entries, indented by `depth` tabs
+    pub(crate) fn write(&self, f: &mut std::fmt::Formatter<'_>, depth: usize) -> std::fmt::Result {
+        let indent = "\t".repeat(depth);
+        for (key, node) in &self.entries {
+            match node {
+                ConfigNode::Value(value) => writeln!(f, "{}{} = {}", indent, key, value)?,
+                ConfigNode::Section(section) => {
+                    writeln!(f)?;
+                    writeln!(f, "{}{} {{", indent, key)?;
+                    section.write(f, depth + 1)?;
+                    writeln!(f, "{}}}", indent)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse a config in the LVM2 text format
+pub(crate) fn parse(input: &str) -> Result<ConfigSection, InvalidMetadataError> {
+    let mut parser = Parser {
+        chars: input.chars().peekable(),
+        line: 1,
+    };
+
+    let root = parser.section_body()?;
+    match parser.next_token()? {
+        None => Ok(root),
+        Some(token) => Err(parser.error(format!("unexpected {:?}", token))),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    String(String),
+    Equals,
+    OpenBrace,
+    CloseBrace,
+    OpenBracket,
+    CloseBracket,
+    Comma,
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, reason: impl Display) -> InvalidMetadataError {
+        InvalidMetadataError(format!("line {}: {}", self.line, reason))
+    }
+
+    /// Parse entries until the end of the current section
+    fn section_body(&mut self) -> Result<ConfigSection, InvalidMetadataError> {
+        let mut section = ConfigSection::default();
+
+        loop {
+            match self.peek_token()? {
+                None | Some(Token::CloseBrace) => return Ok(section),
+                _ => (),
+            }
+
+            let key = match self.next_token()? {
+                Some(Token::Word(key)) => key,
+                token => return Err(self.error(format!("expected a key, got {:?}", token))),
+            };
+
+            match self.next_token()? {
+                Some(Token::Equals) => {
+                    let value = self.value()?;
+                    section.push_value(key, value);
+                }
+                Some(Token::OpenBrace) => {
+                    let subsection = self.section_body()?;
+                    match self.next_token()? {
+                        Some(Token::CloseBrace) => section.push_section(key, subsection),
+                        _ => return Err(self.error(format!("unterminated section {}", key))),
+                    }
+                }
+                token => {
+                    return Err(self.error(format!(
+                        "expected '=' or '{{' after {}, got {:?}",
+                        key, token
+                    )))
+                }
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<MetadataValue, InvalidMetadataError> {
+        match self.next_token()? {
+            Some(Token::String(s)) => Ok(MetadataValue::String(s)),
+            Some(Token::Word(word)) => {
+                if let Ok(i) = word.parse() {
+                    return Ok(MetadataValue::Integer(i));
+                }
+
+                word.parse()
+                    .map(MetadataValue::Float)
+                    .map_err(|_| self.error(format!("invalid number: {}", word)))
+            }
+            Some(Token::OpenBracket) => {
+                let mut values = vec![];
+                loop {
+                    if let Some(Token::CloseBracket) = self.peek_token()? {
+                        self.next_token()?;
+                        return Ok(MetadataValue::Array(values));
+                    }
+
+                    values.push(self.value()?);
+
+                    match self.next_token()? {
+                        Some(Token::Comma) => (),
+                        Some(Token::CloseBracket) => return Ok(MetadataValue::Array(values)),
+                        token => {
+                            return Err(self.error(format!("expected ',' or ']', got {:?}", token)))
+                        }
+                    }
+                }
+            }
+            token => Err(self.error(format!("expected a value, got {:?}", token))),
+        }
+    }
+
+    fn peek_token(&mut self) -> Result<Option<Token>, InvalidMetadataError> {
+        // Tokens are cheap to re-read, so peeking is done on a cloned cursor
+        let (chars, line) = (self.chars.clone(), self.line);
+        let token = self.next_token()?;
+        self.chars = chars;
+        self.line = line;
+
+        Ok(token)
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, InvalidMetadataError> {
+        self.skip_whitespace();
+
+        let c = match self.chars.next() {
+            Some(c) => c,
+            None => return Ok(None),
+        };
+
+        let token = match c {
+            '=' => Token::Equals,
+            '{' => Token::OpenBrace,
+            '}' => Token::CloseBrace,
+            '[' => Token::OpenBracket,
+            ']' => Token::CloseBracket,
+            ',' => Token::Comma,
+            '"' => {
+                let mut s = String::new();
+                loop {
+                    match self.chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match self.chars.next() {
+                            Some(escaped) => s.push(escaped),
+                            None => return Err(self.error("unterminated string")),
+                        },
+                        Some(c) => {
+                            if c == '\n' {
+                                self.line += 1;
+                            }
+
+                            s.push(c)
+                        }
+                        None => return Err(self.error("unterminated string")),
+                    }
+                }
+
+                Token::String(s)
+            }
+            c => {
+                let mut word = String::from(c);
+                while let Some(&c) = self.chars.peek() {
+                    if c.is_whitespace() || "={}[],\"#".contains(c) {
+                        break;
+                    }
+
+                    word.push(c);
+                    self.chars.next();
+                }
+
+                Token::Word(word)
+            }
+        };
+
+        Ok(Some(token))
+    }
+
+    /// Skip whitespace and comments
+    fn skip_whitespace(&mut self) {
+        while let Some(&c) = self.chars.peek() {
+            match c {
+                '\n' => self.line += 1,
+                '#' => {
+                    while let Some(&c) = self.chars.peek() {
+                        if c == '\n' {
+                            break;
+                        }
+
+                        self.chars.next();
+                    }
+
+                    continue;
+                }
+                c if c.is_whitespace() => (),
+                _ => return,
+            }
+
+            self.chars.next();
+        }
+    }
+}
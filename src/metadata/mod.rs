@@ -0,0 +1,835 @@
+mod config;
+
+pub use config::{InvalidMetadataError, MetadataValue};
+
+use std::{fmt::Display, path::PathBuf, str::FromStr};
+
+use crate::{ResourceName, ResourceUUID};
+use config::{ConfigSection, MetadataValue as Value};
+
+/// The size of a sector, which is the unit of most sizes in the metadata
+pub const SECTOR_SIZE: u64 = 512;
+
+/// Represents a complete metadata file for a single volume group, in the text format
+/// produced by `vgcfgbackup` and stored in `/etc/lvm/backup` and `/etc/lvm/archive`.
+///
+/// Use [str::parse] to parse a file, and [ToString::to_string] to serialize it again.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetadataFile {
+    /// The type of the file's contents, usually `Text Format Volume Group`
+    pub contents: String,
+    pub version: u64,
+    pub description: Option<String>,
+    pub creation_host: Option<String>,
+
+    /// When the file was written, in seconds since the unix epoch
+    pub creation_time: Option<u64>,
+
+    pub volume_group: VolumeGroupMetadata,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VolumeGroupMetadata {
+    pub name: ResourceName,
+    pub id: ResourceUUID,
+
+    /// The sequence number of the metadata, incremented on each change
+    pub seqno: u64,
+    pub format: Option<String>,
+    pub status: Vec<String>,
+    pub flags: Vec<String>,
+    pub tags: Vec<String>,
+    pub system_id: Option<String>,
+    pub lock_type: Option<String>,
+    pub extent_size_sectors: u64,
+    pub max_lv: u64,
+    pub max_pv: u64,
+    pub metadata_copies: u64,
+    pub physical_volumes: Vec<PhysicalVolumeMetadata>,
+    pub logical_volumes: Vec<LogicalVolumeMetadata>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PhysicalVolumeMetadata {
+    /// The name of the physical volume within the metadata (e.g. `pv0`)
+    pub name: ResourceName,
+    pub id: ResourceUUID,
+
+    /// The last known path of the device, which is only a hint
+    pub device: Option<PathBuf>,
+    pub status: Vec<String>,
+    pub flags: Vec<String>,
+    pub tags: Vec<String>,
+    pub dev_size_sectors: u64,
+
+    /// The offset of the first extent, in sectors
+    pub pe_start: u64,
+    pub pe_count: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogicalVolumeMetadata {
+    pub name: ResourceName,
+    pub id: ResourceUUID,
+    pub status: Vec<String>,
+    pub flags: Vec<String>,
+    pub tags: Vec<String>,
+    pub creation_host: Option<String>,
+
+    /// When the logical volume was created, in seconds since the unix epoch
+    pub creation_time: Option<u64>,
+    pub allocation_policy: Option<String>,
+    pub segments: Vec<SegmentMetadata>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SegmentMetadata {
+    pub start_extent: u64,
+    pub extent_count: u64,
+
+    /// The type of the segment (e.g. `striped`, `raid1`, `thin-pool`)
+    pub segment_type: String,
+    pub tags: Vec<String>,
+    pub stripe_size_sectors: Option<u64>,
+    pub stripes: Vec<StripeMetadata>,
+
+    /// Any other settings, which depend on the type of the segment (e.g. `raids`, `thin_pool`)
+    pub settings: Vec<(String, MetadataValue)>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StripeMetadata {
+    /// The name of the physical volume within the metadata (e.g. `pv0`)
+    pub physical_volume: ResourceName,
+    pub start_extent: u64,
+}
+
+impl VolumeGroupMetadata {
+    pub fn extent_size_bytes(&self) -> u64 {
+        self.extent_size_sectors * SECTOR_SIZE
+    }
+
+    /// Get a physical volume by its name within the metadata (e.g. `pv0`)
+    pub fn physical_volume(&self, name: &str) -> Option<&PhysicalVolumeMetadata> {
+        self.physical_volumes
+            .iter()
+            .find(|pv| pv.name.as_str() == name)
+    }
+
+    /// Get a logical volume by its name
+    pub fn logical_volume(&self, name: &str) -> Option<&LogicalVolumeMetadata> {
+        self.logical_volumes
+            .iter()
+            .find(|lv| lv.name.as_str() == name)
+    }
+}
+
+impl LogicalVolumeMetadata {
+    /// The total amount of extents used by all segments
+    pub fn extent_count(&self) -> u64 {
+        self.segments.iter().map(|seg| seg.extent_count).sum()
+    }
+}
+
+impl FromStr for MetadataFile {
+    type Err = InvalidMetadataError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let root = config::parse(s)?;
+
+        let mut groups = root.sections();
+        let (name, vg) = groups
+            .next()
+            .ok_or(InvalidMetadataError("missing volume group section".into()))?;
+
+        if let Some((other, _)) = groups.next() {
+            return Err(InvalidMetadataError(format!(
+                "expected a single volume group section, found {} and {}",
+                name, other
+            )));
+        }
+
+        Ok(MetadataFile {
+            contents: get_string(&root, "contents")?,
+            version: get_integer(&root, "version")?,
+            description: get_optional_string(&root, "description")?,
+            creation_host: get_optional_string(&root, "creation_host")?,
+            creation_time: get_optional_integer(&root, "creation_time")?,
+            volume_group: parse_vg(name, vg)?,
+        })
+    }
+}
+
+impl Display for MetadataFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut root = ConfigSection::default();
+        root.push_value("contents", Value::String(self.contents.clone()));
+        root.push_value("version", integer(self.version));
+        push_optional_string(&mut root, "description", &self.description);
+        push_optional_string(&mut root, "creation_host", &self.creation_host);
+        if let Some(time) = self.creation_time {
+            root.push_value("creation_time", integer(time));
+        }
+
+        let vg = &self.volume_group;
+        root.push_section(vg.name.to_string(), write_vg(vg));
+
+        root.write(f, 0)
+    }
+}
+
+fn parse_vg(name: &str, vg: &ConfigSection) -> Result<VolumeGroupMetadata, InvalidMetadataError> {
+    let physical_volumes = vg
+        .section("physical_volumes")
+        .map(|pvs| {
+            pvs.sections()
+                .map(|(name, pv)| parse_pv(name, pv))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+    let logical_volumes = vg
+        .section("logical_volumes")
+        .map(|lvs| {
+            lvs.sections()
+                .map(|(name, lv)| parse_lv(name, lv))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+    Ok(VolumeGroupMetadata {
+        name: parse_name(name)?,
+        id: parse_uuid(&get_string(vg, "id")?)?,
+        seqno: get_integer(vg, "seqno")?,
+        format: get_optional_string(vg, "format")?,
+        status: get_string_list(vg, "status")?,
+        flags: get_string_list(vg, "flags")?,
+        tags: get_string_list(vg, "tags")?,
+        system_id: get_optional_string(vg, "system_id")?,
+        lock_type: get_optional_string(vg, "lock_type")?,
+        extent_size_sectors: get_integer(vg, "extent_size")?,
+        max_lv: get_optional_integer(vg, "max_lv")?.unwrap_or_default(),
+        max_pv: get_optional_integer(vg, "max_pv")?.unwrap_or_default(),
+        metadata_copies: get_optional_integer(vg, "metadata_copies")?.unwrap_or_default(),
+        physical_volumes,
+        logical_volumes,
+    })
+}
+
+fn write_vg(vg: &VolumeGroupMetadata) -> ConfigSection {
+    let mut section = ConfigSection::default();
+    section.push_value("id", Value::String(vg.id.to_string()));
+    section.push_value("seqno", integer(vg.seqno));
+    push_optional_string(&mut section, "format", &vg.format);
+    section.push_value("status", string_list(&vg.status));
+    section.push_value("flags", string_list(&vg.flags));
+    if !vg.tags.is_empty() {
+        section.push_value("tags", string_list(&vg.tags));
+    }
+
+    push_optional_string(&mut section, "system_id", &vg.system_id);
+    push_optional_string(&mut section, "lock_type", &vg.lock_type);
+    section.push_value("extent_size", integer(vg.extent_size_sectors));
+    section.push_value("max_lv", integer(vg.max_lv));
+    section.push_value("max_pv", integer(vg.max_pv));
+    section.push_value("metadata_copies", integer(vg.metadata_copies));
+
+    let mut pvs = ConfigSection::default();
+    for pv in &vg.physical_volumes {
+        pvs.push_section(pv.name.to_string(), write_pv(pv));
+    }
+
+    section.push_section("physical_volumes", pvs);
+
+    if !vg.logical_volumes.is_empty() {
+        let mut lvs = ConfigSection::default();
+        for lv in &vg.logical_volumes {
+            lvs.push_section(lv.name.to_string(), write_lv(lv));
+        }
+
+        section.push_section("logical_volumes", lvs);
+    }
+
+    section
+}
+
+fn parse_pv(
+    name: &str,
+    pv: &ConfigSection,
+) -> Result<PhysicalVolumeMetadata, InvalidMetadataError> {
+    Ok(PhysicalVolumeMetadata {
+        name: parse_name(name)?,
+        id: parse_uuid(&get_string(pv, "id")?)?,
+        device: get_optional_string(pv, "device")?.map(PathBuf::from),
+        status: get_string_list(pv, "status")?,
+        flags: get_string_list(pv, "flags")?,
+        tags: get_string_list(pv, "tags")?,
+        dev_size_sectors: get_integer(pv, "dev_size")?,
+        pe_start: get_integer(pv, "pe_start")?,
+        pe_count: get_integer(pv, "pe_count")?,
+    })
+}
+
+fn write_pv(pv: &PhysicalVolumeMetadata) -> ConfigSection {
+    let mut section = ConfigSection::default();
+    section.push_value("id", Value::String(pv.id.to_string()));
+    if let Some(device) = &pv.device {
+        section.push_value("device", Value::String(device.to_string_lossy().into()));
+    }
+
+    section.push_value("status", string_list(&pv.status));
+    section.push_value("flags", string_list(&pv.flags));
+    if !pv.tags.is_empty() {
+        section.push_value("tags", string_list(&pv.tags));
+    }
+
+    section.push_value("dev_size", integer(pv.dev_size_sectors));
+    section.push_value("pe_start", integer(pv.pe_start));
+    section.push_value("pe_count", integer(pv.pe_count));
+
+    section
+}
+
+fn parse_lv(name: &str, lv: &ConfigSection) -> Result<LogicalVolumeMetadata, InvalidMetadataError> {
+    let mut segments = lv
+        .sections()
+        .filter(|(key, _)| key.starts_with("segment"))
+        .map(|(_, seg)| parse_segment(seg))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    segments.sort_by_key(|seg| seg.start_extent);
+
+    if let Some(count) = get_optional_integer(lv, "segment_count")? {
+        if count != segments.len() as u64 {
+            return Err(InvalidMetadataError(format!(
+                "logical volume {} declares {} segment(s), found {}",
+                name,
+                count,
+                segments.len()
+            )));
+        }
+    }
+
+    Ok(LogicalVolumeMetadata {
+        name: parse_name(name)?,
+        id: parse_uuid(&get_string(lv, "id")?)?,
+        status: get_string_list(lv, "status")?,
+        flags: get_string_list(lv, "flags")?,
+        tags: get_string_list(lv, "tags")?,
+        creation_host: get_optional_string(lv, "creation_host")?,
+        creation_time: get_optional_integer(lv, "creation_time")?,
+        allocation_policy: get_optional_string(lv, "allocation_policy")?,
+        segments,
+    })
+}
+
+fn write_lv(lv: &LogicalVolumeMetadata) -> ConfigSection {
+    let mut section = ConfigSection::default();
+    section.push_value("id", Value::String(lv.id.to_string()));
+    section.push_value("status", string_list(&lv.status));
+    section.push_value("flags", string_list(&lv.flags));
+    if !lv.tags.is_empty() {
+        section.push_value("tags", string_list(&lv.tags));
+    }
+
+    if let Some(time) = lv.creation_time {
+        section.push_value("creation_time", integer(time));
+    }
+
+    push_optional_string(&mut section, "creation_host", &lv.creation_host);
+    push_optional_string(&mut section, "allocation_policy", &lv.allocation_policy);
+    section.push_value("segment_count", integer(lv.segments.len() as u64));
+
+    for (i, seg) in lv.segments.iter().enumerate() {
+        section.push_section(format!("segment{}", i + 1), write_segment(seg));
+    }
+
+    section
+}
+
+fn parse_segment(seg: &ConfigSection) -> Result<SegmentMetadata, InvalidMetadataError> {
+    const KNOWN_KEYS: [&str; 7] = [
+        "start_extent",
+        "extent_count",
+        "type",
+        "tags",
+        "stripe_size",
+        "stripe_count",
+        "stripes",
+    ];
+
+    // Stripes are stored as a flat list of `"pv", start_extent` pairs
+    let stripes = match seg.value("stripes") {
+        None => vec![],
+        Some(Value::Array(values)) => values
+            .chunks(2)
+            .map(|pair| match pair {
+                [Value::String(pv), Value::Integer(start)] if *start >= 0 => Ok(StripeMetadata {
+                    physical_volume: parse_name(pv)?,
+                    start_extent: *start as u64,
+                }),
+                _ => Err(InvalidMetadataError(format!(
+                    "invalid stripe entry: {:?}",
+                    pair
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        Some(value) => {
+            return Err(InvalidMetadataError(format!(
+                "stripes must be an array: {}",
+                value
+            )))
+        }
+    };
+
+    if let Some(count) = get_optional_integer(seg, "stripe_count")? {
+        if count != stripes.len() as u64 {
+            return Err(InvalidMetadataError(format!(
+                "segment declares {} stripe(s), found {}",
+                count,
+                stripes.len()
+            )));
+        }
+    }
+
+    Ok(SegmentMetadata {
+        start_extent: get_integer(seg, "start_extent")?,
+        extent_count: get_integer(seg, "extent_count")?,
+        segment_type: get_string(seg, "type")?,
+        tags: get_string_list(seg, "tags")?,
+        stripe_size_sectors: get_optional_integer(seg, "stripe_size")?,
+        stripes,
+        settings: seg
+            .values()
+            .filter(|(key, _)| !KNOWN_KEYS.contains(key))
+            .map(|(key, value)| (key.into(), value.clone()))
+            .collect(),
+    })
+}
+
+fn write_segment(seg: &SegmentMetadata) -> ConfigSection {
+    let mut section = ConfigSection::default();
+    section.push_value("start_extent", integer(seg.start_extent));
+    section.push_value("extent_count", integer(seg.extent_count));
+    section.push_value("type", Value::String(seg.segment_type.clone()));
+    if !seg.tags.is_empty() {
+        section.push_value("tags", string_list(&seg.tags));
+    }
+
+    if !seg.stripes.is_empty() {
+        section.push_value("stripe_count", integer(seg.stripes.len() as u64));
+    }
+
+    if let Some(size) = seg.stripe_size_sectors {
+        section.push_value("stripe_size", integer(size));
+    }
+
+    if !seg.stripes.is_empty() {
+        section.push_value(
+            "stripes",
+            Value::Array(
+                seg.stripes
+                    .iter()
+                    .flat_map(|stripe| {
+                        [
+                            Value::String(stripe.physical_volume.to_string()),
+                            integer(stripe.start_extent),
+                        ]
+                    })
+                    .collect(),
+            ),
+        );
+    }
+
+    for (key, value) in &seg.settings {
+        section.push_value(key.clone(), value.clone());
+    }
+
+    section
+}
+
+fn parse_name(name: &str) -> Result<ResourceName, InvalidMetadataError> {
+    ResourceName::try_from(name).map_err(|err| InvalidMetadataError(err.to_string()))
+}
+
+fn parse_uuid(uuid: &str) -> Result<ResourceUUID, InvalidMetadataError> {
+    ResourceUUID::try_from(uuid).map_err(|err| InvalidMetadataError(err.to_string()))
+}
+
+fn get_optional_string(
+    section: &ConfigSection,
+    key: &str,
+) -> Result<Option<String>, InvalidMetadataError> {
+    match section.value(key) {
+        None => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.clone())),
+        Some(value) => Err(InvalidMetadataError(format!(
+            "{} must be a string: {}",
+            key, value
+        ))),
+    }
+}
+
+fn get_string(section: &ConfigSection, key: &str) -> Result<String, InvalidMetadataError> {
+    get_optional_string(section, key)?.ok_or(InvalidMetadataError(format!(
+        "missing required key {}",
+        key
+    )))
+}
+
+fn get_optional_integer(
+    section: &ConfigSection,
+    key: &str,
+) -> Result<Option<u64>, InvalidMetadataError> {
+    match section.value(key) {
+        None => Ok(None),
+        Some(Value::Integer(i)) if *i >= 0 => Ok(Some(*i as u64)),
+        Some(value) => Err(InvalidMetadataError(format!(
+            "{} must be a positive integer: {}",
+            key, value
+        ))),
+    }
+}
+
+fn get_integer(section: &ConfigSection, key: &str) -> Result<u64, InvalidMetadataError> {
+    get_optional_integer(section, key)?.ok_or(InvalidMetadataError(format!(
+        "missing required key {}",
+        key
+    )))
+}
+
+fn get_string_list(
+    section: &ConfigSection,
+    key: &str,
+) -> Result<Vec<String>, InvalidMetadataError> {
+    match section.value(key) {
+        None => Ok(vec![]),
+        Some(Value::Array(values)) => values
+            .iter()
+            .map(|value| match value {
+                Value::String(s) => Ok(s.clone()),
+                _ => Err(InvalidMetadataError(format!(
+                    "{} must only contain strings: {}",
+                    key, value
+                ))),
+            })
+            .collect(),
+        Some(value) => Err(InvalidMetadataError(format!(
+            "{} must be an array: {}",
+            key, value
+        ))),
+    }
+}
+
+fn push_optional_string(section: &mut ConfigSection, key: &str, value: &Option<String>) {
+    if let Some(value) = value {
+        section.push_value(key, Value::String(value.clone()));
+    }
+}
+
+fn integer(i: u64) -> Value {
+    Value::Integer(i as i64)
+}
+
+fn string_list(values: &[String]) -> Value {
+    Value::Array(values.iter().cloned().map(Value::String).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A backup written by `vgcfgbackup`, holding striped, raid and thin volumes
+    const BACKUP: &str = r#"# Generated by LVM2 version 2.03.16(2) (2022-05-18): Sat Oct 17 09:12:44 2026
+
+contents = "Text Format Volume Group"
+version = 1
+
+description = "Created *after* executing 'vgcfgbackup --file /tmp/vg0.backup vg0'"
+
+creation_host = "storage01"	# Linux storage01 6.1.0-26-amd64 #1 SMP PREEMPT_DYNAMIC Debian 6.1.112-1 x86_64
+creation_time = 1792228364	# Sat Oct 17 09:12:44 2026
+
+vg0 {
+	id = "Fq3t1C-aYtM-kP2v-Ew0R-dG7s-Lq9B-x1Zc7m"
+	seqno = 14
+	format = "lvm2"			# informational
+	status = ["RESIZEABLE", "READ", "WRITE"]
+	flags = []
+	extent_size = 8192		# 4 Megabytes
+	max_lv = 0
+	max_pv = 0
+	metadata_copies = 0
+
+	physical_volumes {
+
+		pv0 {
+			id = "m3K0dP-1sQw-Zt7e-Hn4R-cV2b-Yx8L-o5Ua9i"
+			device = "/dev/sdb"	# Hint only
+
+			status = ["ALLOCATABLE"]
+			flags = []
+			dev_size = 20971520	# 10 Gigabytes
+			pe_start = 2048
+			pe_count = 2559	# 9.99609 Gigabytes
+		}
+
+		pv1 {
+			id = "T8wq2L-Ab3c-De4f-Gh5i-Jk6l-Mn7o-Pq8rSt"
+			device = "/dev/sdc"	# Hint only
+
+			status = ["ALLOCATABLE"]
+			flags = []
+			dev_size = 20971520	# 10 Gigabytes
+			pe_start = 2048
+			pe_count = 2559	# 9.99609 Gigabytes
+		}
+	}
+
+	logical_volumes {
+
+		data {
+			id = "cD1eF2-gH3i-jK4l-mN5o-pQ6r-sT7u-vW8xY9"
+			status = ["READ", "WRITE", "VISIBLE"]
+			flags = []
+			tags = ["app", "tier:gold"]
+			creation_time = 1792228000	# 2026-10-17 09:06:40 +0000
+			creation_host = "storage01"
+			segment_count = 1
+
+			segment1 {
+				start_extent = 0
+				extent_count = 256	# 1 Gigabytes
+
+				type = "striped"
+				stripe_count = 2
+				stripe_size = 128	# 64 Kilobytes
+
+				stripes = [
+					"pv0", 0,
+					"pv1", 0
+				]
+			}
+		}
+
+		mirror {
+			id = "Aa1Bb2-Cc3D-d4Ee-5Ff6-Gg7H-h8Ii-9Jj0Kk"
+			status = ["READ", "WRITE", "VISIBLE"]
+			flags = []
+			creation_time = 1792228100	# 2026-10-17 09:08:20 +0000
+			creation_host = "storage01"
+			segment_count = 1
+
+			segment1 {
+				start_extent = 0
+				extent_count = 64	# 256 Megabytes
+
+				type = "raid1"
+				device_count = 2
+				region_size = 4096	# 2 Megabytes
+
+				raids = [
+					"mirror_rmeta_0", "mirror_rimage_0",
+					"mirror_rmeta_1", "mirror_rimage_1"
+				]
+			}
+		}
+
+		pool {
+			id = "Zz9Yy8-Xx7W-w6Vv-5Uu4-Tt3S-s2Rr-1Qq0Pp"
+			status = ["READ", "WRITE", "VISIBLE"]
+			flags = []
+			creation_time = 1792228200	# 2026-10-17 09:10:00 +0000
+			creation_host = "storage01"
+			segment_count = 1
+
+			segment1 {
+				start_extent = 0
+				extent_count = 128	# 512 Megabytes
+
+				type = "thin-pool"
+				metadata = "pool_tmeta"
+				pool = "pool_tdata"
+				transaction_id = 1
+				chunk_size = 128	# 64 Kilobytes
+				discards = "passdown"
+				zero_new_blocks = 1
+			}
+		}
+
+		thin {
+			id = "Q1w2E3-r4T5-y6U7-i8O9-p0As-dFgh-JkLzXc"
+			status = ["READ", "WRITE", "VISIBLE"]
+			flags = []
+			creation_time = 1792228300	# 2026-10-17 09:11:40 +0000
+			creation_host = "storage01"
+			segment_count = 1
+
+			segment1 {
+				start_extent = 0
+				extent_count = 512	# 2 Gigabytes
+
+				type = "thin"
+				thin_pool = "pool"
+				transaction_id = 0
+				device_id = 1
+			}
+		}
+
+		mirror_rmeta_0 {
+			id = "Mm1Nn2-Oo3P-p4Qq-5Rr6-Ss7T-t8Uu-9Vv0Ww"
+			status = ["READ", "WRITE"]
+			flags = []
+			creation_time = 1792228100	# 2026-10-17 09:08:20 +0000
+			creation_host = "storage01"
+			segment_count = 1
+
+			segment1 {
+				start_extent = 0
+				extent_count = 1	# 4 Megabytes
+
+				type = "striped"
+				stripe_count = 1	# linear
+
+				stripes = [
+					"pv0", 128
+				]
+			}
+		}
+
+		mirror_rimage_0 {
+			id = "Bb1Cc2-Dd3E-e4Ff-5Gg6-Hh7I-i8Jj-9Kk0Ll"
+			status = ["READ", "WRITE"]
+			flags = []
+			creation_time = 1792228100	# 2026-10-17 09:08:20 +0000
+			creation_host = "storage01"
+			segment_count = 1
+
+			segment1 {
+				start_extent = 0
+				extent_count = 64	# 256 Megabytes
+
+				type = "striped"
+				stripe_count = 1	# linear
+
+				stripes = [
+					"pv0", 129
+				]
+			}
+		}
+	}
+
+}
+"#;
+
+    fn parse_err(contents: &str) -> String {
+        contents
+            .parse::<MetadataFile>()
+            .expect_err("metadata should be invalid")
+            .to_string()
+    }
+
+    #[test]
+    fn parses_backup() {
+        let file: MetadataFile = BACKUP.parse().unwrap();
+        assert_eq!(file.contents, "Text Format Volume Group");
+        assert_eq!(file.creation_time, Some(1792228364));
+
+        let vg = &file.volume_group;
+        assert_eq!(vg.name.as_str(), "vg0");
+        assert_eq!(vg.extent_size_bytes(), 4 * 1024 * 1024);
+        assert_eq!(vg.physical_volumes.len(), 2);
+        assert_eq!(
+            vg.physical_volume("pv1").unwrap().device,
+            Some(PathBuf::from("/dev/sdc"))
+        );
+
+        let data = vg.logical_volume("data").unwrap();
+        assert_eq!(data.tags, vec!["app", "tier:gold"]);
+        let seg = &data.segments[0];
+        assert_eq!(seg.segment_type, "striped");
+        assert_eq!(seg.stripe_size_sectors, Some(128));
+        assert_eq!(
+            seg.stripes,
+            vec![
+                StripeMetadata {
+                    physical_volume: "pv0".try_into().unwrap(),
+                    start_extent: 0
+                },
+                StripeMetadata {
+                    physical_volume: "pv1".try_into().unwrap(),
+                    start_extent: 0
+                },
+            ]
+        );
+
+        let mirror = &vg.logical_volume("mirror").unwrap().segments[0];
+        assert_eq!(mirror.segment_type, "raid1");
+        assert!(mirror.stripes.is_empty());
+        assert!(mirror
+            .settings
+            .contains(&("device_count".into(), MetadataValue::Integer(2))));
+
+        let pool = &vg.logical_volume("pool").unwrap().segments[0];
+        assert!(pool
+            .settings
+            .contains(&("discards".into(), MetadataValue::String("passdown".into()))));
+
+        let thin = vg.logical_volume("thin").unwrap();
+        assert_eq!(thin.extent_count(), 512);
+    }
+
+    #[test]
+    fn round_trips_backup() {
+        let file: MetadataFile = BACKUP.parse().unwrap();
+        let written = file.to_string();
+        let reparsed: MetadataFile = written.parse().unwrap();
+
+        assert_eq!(file, reparsed);
+        assert_eq!(written, reparsed.to_string());
+    }
+
+    #[test]
+    fn rejects_unterminated_string() {
+        let err = parse_err("contents = \"Text Format Volume Group\nversion = 1\n");
+        assert!(err.contains("unterminated string"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_unterminated_section() {
+        let truncated = &BACKUP[..BACKUP.rfind('}').unwrap()];
+        let err = parse_err(truncated);
+        assert!(err.contains("unterminated section vg0"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_stripe_count_mismatch() {
+        let invalid = BACKUP.replacen("stripe_count = 2", "stripe_count = 3", 1);
+        let err = parse_err(&invalid);
+        assert!(err.contains("declares 3 stripe(s), found 2"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_non_array_stripes() {
+        let invalid = BACKUP.replacen(
+            "stripes = [\n\t\t\t\t\t\"pv0\", 128\n\t\t\t\t]",
+            "stripes = \"pv0\"",
+            1,
+        );
+        assert_ne!(invalid, BACKUP);
+
+        let err = parse_err(&invalid);
+        assert!(err.contains("stripes must be an array"), "{}", err);
+    }
+
+    #[test]
+    fn escapes_strings() {
+        let value = MetadataValue::String("say \"hi\" \\ bye".into());
+        let written = format!("key = {}\n", value);
+        let parsed = config::parse(&written).unwrap();
+
+        assert_eq!(parsed.value("key"), Some(&value));
+    }
+}
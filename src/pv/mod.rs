@@ -0,0 +1,282 @@
+mod pv_attributes;
+mod pv_segment;
+
+pub use pv_attributes::*;
+pub use pv_segment::*;
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use serde_aux::field_attributes::deserialize_number_from_string;
+
+use crate::{
+    deserialize_optional, deserialize_tags, devices::DeviceEntry, error::LVMError, run_cmd,
+    ResourceCapacity, ResourceName, ResourceSelector, ResourceUUID,
+};
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PhysicalVolume {
+    /// The path to the underlying block device
+    #[serde(rename = "pv_name")]
+    pub name: PathBuf,
+
+    #[serde(rename = "pv_uuid")]
+    pub uuid: ResourceUUID,
+
+    /// The owning volume group, if any
+    #[serde(rename = "vg_name", deserialize_with = "deserialize_optional")]
+    pub volume_group_name: Option<ResourceName>,
+
+    #[serde(rename = "pv_size")]
+    pub capacity_bytes: ResourceCapacity,
+
+    #[serde(
+        rename = "pv_free",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub space_free_bytes: usize,
+
+    #[serde(
+        rename = "pv_mda_count",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub metadata_area_count: usize,
+
+    /// The amount of metadata areas in use, which excludes those ignored through `--metadataignore`
+    #[serde(
+        rename = "pv_mda_used_count",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub metadata_area_used_count: usize,
+
+    #[serde(rename = "pv_tags", deserialize_with = "deserialize_tags")]
+    pub tags: Vec<String>,
+
+    #[serde(rename = "pv_attr", deserialize_with = "deserialize_pv_attrs")]
+    pub attributes: PhysicalVolumeAttributes,
+}
+
+impl PhysicalVolume {
+    /// Initialize a block device as a [PhysicalVolume]
+    pub fn create(
+        device: impl AsRef<Path>,
+        opts: PVCreateOptions,
+    ) -> Result<PhysicalVolume, LVMError> {
+        let device = device.as_ref();
+
+        let mut args: Vec<String> = vec![];
+        if let Some(copies) = opts.metadata_copies {
+            args.extend(["--pvmetadatacopies".into(), copies.to_string()]);
+        }
+
+        if let Some(ignore) = opts.metadata_ignore {
+            args.extend([
+                "--metadataignore".into(),
+                if ignore { "y" } else { "n" }.into(),
+            ]);
+        }
+
+        args.push(device.to_string_lossy().into());
+
+        // Create the volume (has no output)
+        run_cmd::<String>("pvcreate", &args, None::<&str>)?;
+
+        if opts.register_device {
+            DeviceEntry::add_device(device)?;
+        }
+
+        Self::from_id(device)
+    }
+
+    /// Get a [PhysicalVolume] from the path of its block device
+    pub fn from_id(device: impl AsRef<Path>) -> Result<PhysicalVolume, LVMError> {
+        let device = device.as_ref().to_string_lossy();
+        let args = vec![
+            "--nolocking",
+            "--options",
+            "+pv_all",
+            "--units",
+            "b",
+            "--nosuffix",
+            &device,
+        ];
+
+        run_cmd("pvs", &args, Some("pv")).and_then(|mut pvs| {
+            pvs.pop().ok_or(LVMError::NotFound {
+                resource: device.to_string(),
+            })
+        })
+    }
+
+    /// List all [PhysicalVolume]s available on this system
+    pub fn list() -> Result<Vec<PhysicalVolume>, LVMError> {
+        let args = vec![
+            "--nolocking",
+            "--options",
+            "+pv_all",
+            "--units",
+            "b",
+            "--nosuffix",
+            // Deterministically return sorted by `pv_name`
+            "--sort",
+            "pv_name",
+        ];
+
+        run_cmd("pvs", &args, Some("pv"))
+    }
+
+    /// List all [PhysicalVolume]s belonging to a specific volume group
+    pub fn list_for_vg(volume_group: &ResourceName) -> Result<Vec<PhysicalVolume>, LVMError> {
+        let selector = format!("vg_name={}", volume_group);
+        let args = vec![
+            "--nolocking",
+            "--options",
+            "+pv_all",
+            "--units",
+            "b",
+            "--nosuffix",
+            // Deterministically return sorted by `pv_name`
+            "--sort",
+            "pv_name",
+            "--select",
+            &selector,
+        ];
+
+        run_cmd("pvs", &args, Some("pv"))
+    }
+
+    /// List the segments of all [PhysicalVolume]s belonging to a specific volume group,
+    /// including unallocated ones
+    pub fn segments_for_vg(
+        volume_group: &ResourceName,
+    ) -> Result<Vec<PhysicalVolumeSegment>, LVMError> {
+        let selector = format!("vg_name={}", volume_group);
+        let args = vec![
+            "--nolocking",
+            "--segments",
+            "--options",
+            "pv_name,pv_uuid,pvseg_start,pvseg_size,lv_name",
+            // Deterministically return sorted by `pv_name`, then `pvseg_start`
+            "--sort",
+            "pv_name,pvseg_start",
+            "--select",
+            &selector,
+        ];
+
+        run_cmd("pvs", &args, Some("pv"))
+    }
+
+    /// Change the [PhysicalVolume], refreshing its state afterwards.
+    pub fn change(&mut self, opts: PVChangeOptions) -> Result<(), LVMError> {
+        let mut args: Vec<String> = vec![];
+        if let Some(allocatable) = opts.allocatable {
+            args.extend([
+                "--allocatable".into(),
+                if allocatable { "y" } else { "n" }.into(),
+            ]);
+        }
+
+        if let Some(ignore) = opts.metadata_ignore {
+            args.extend([
+                "--metadataignore".into(),
+                if ignore { "y" } else { "n" }.into(),
+            ]);
+        }
+
+        args.extend(
+            opts.add_tags
+                .into_iter()
+                .flat_map(|t| ["--addtag".into(), t]),
+        );
+        args.extend(
+            opts.remove_tags
+                .into_iter()
+                .flat_map(|t| ["--deltag".into(), t]),
+        );
+
+        if opts.regenerate_uuid {
+            args.push("--uuid".into());
+        }
+
+        args.push(self.name.to_string_lossy().into());
+
+        // Change the volume (has no output)
+        run_cmd::<String>("pvchange", &args, None::<&str>)?;
+
+        *self = Self::from_id(&self.name)?;
+
+        Ok(())
+    }
+
+    /// Allow or disallow the allocation of new extents on this [PhysicalVolume]
+    pub fn set_allocatable(&mut self, allocatable: bool) -> Result<(), LVMError> {
+        self.change(PVChangeOptions {
+            allocatable: Some(allocatable),
+            ..Default::default()
+        })
+    }
+
+    /// Generate a new random UUID for this [PhysicalVolume]
+    pub fn regenerate_uuid(&mut self) -> Result<(), LVMError> {
+        self.change(PVChangeOptions {
+            regenerate_uuid: true,
+            ..Default::default()
+        })
+    }
+}
+
+impl ResourceSelector for PhysicalVolume {
+    fn from_uuid(uuid: &ResourceUUID) -> Result<Self, LVMError>
+    where
+        Self: Sized + std::fmt::Debug + serde::de::DeserializeOwned,
+    {
+        let selector = format!("pv_uuid={}", uuid);
+        let args = vec![
+            "--nolocking",
+            "--options",
+            "+pv_all",
+            "--units",
+            "b",
+            "--nosuffix",
+            "--select",
+            &selector,
+        ];
+
+        run_cmd("pvs", &args, Some("pv")).and_then(|mut pvs| {
+            pvs.pop().ok_or(LVMError::NotFound {
+                resource: uuid.to_string(),
+            })
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PVChangeOptions {
+    /// Whether new extents may be allocated on the physical volume
+    pub allocatable: Option<bool>,
+
+    /// Whether LVM2 should ignore the metadata areas on the physical volume
+    pub metadata_ignore: Option<bool>,
+
+    /// Tags to add to the physical volume
+    pub add_tags: Vec<String>,
+
+    /// Tags to remove from the physical volume
+    pub remove_tags: Vec<String>,
+
+    /// Whether to generate a new random UUID for the physical volume
+    pub regenerate_uuid: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PVCreateOptions {
+    /// The amount of metadata areas to keep on the physical volume (0, 1 or 2)
+    pub metadata_copies: Option<usize>,
+
+    /// Whether LVM2 should ignore the metadata areas on the physical volume
+    pub metadata_ignore: Option<bool>,
+
+    /// Whether to add the device to the LVM2 devices file, so that it stays
+    /// visible to LVM2 when the devices file is enabled
+    pub register_device: bool,
+}
@@ -0,0 +1,90 @@
+#[derive(Clone, Debug)]
+pub struct PhysicalVolumeAttributes {
+    /// Iff another device with the same PV UUID was found on the system.
+    pub is_duplicate: bool,
+
+    /// Iff new extents can be allocated on this physical volume.
+    pub is_allocatable: bool,
+
+    /// Iff this physical volume is used by a volume group that is not known to the system.
+    pub is_used: bool,
+
+    /// Iff the volume group owning this physical volume has been exported.
+    pub is_exported: bool,
+
+    /// Iff this physical volume is missing from the system.
+    pub is_missing: bool,
+}
+
+/// Deserialize a [PhysicalVolume]'s attributes from an attribute string
+pub(crate) fn deserialize_pv_attrs<'de, D>(
+    deserializer: D,
+) -> Result<PhysicalVolumeAttributes, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    // define a visitor that deserializes
+    // `ActualData` encoded as json within a string
+    struct AttrStringVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for AttrStringVisitor {
+        type Value = PhysicalVolumeAttributes;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a string containing pv attribute data")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            #[cfg(feature = "logging")]
+            log::debug!("deserializing attr string: {}", v);
+
+            let mut chars = v.chars();
+
+            let (is_duplicate, is_allocatable, is_used) = match chars
+                .next()
+                .ok_or(E::custom("could not get volume allocation attribute"))?
+            {
+                '-' => (false, false, false),
+                'd' => (true, false, false),
+                'a' => (false, true, false),
+                'u' => (false, false, true),
+
+                _ => return Err(E::custom("invalid flag for IsAllocatable")),
+            };
+
+            let is_exported = match chars
+                .next()
+                .ok_or(E::custom("could not get volume export attribute"))?
+            {
+                '-' => false,
+                'x' => true,
+
+                _ => return Err(E::custom("invalid flag for IsExported")),
+            };
+
+            let is_missing = match chars
+                .next()
+                .ok_or(E::custom("could not get volume missing attribute"))?
+            {
+                '-' => false,
+                'm' => true,
+
+                _ => return Err(E::custom("invalid flag for IsMissing")),
+            };
+
+            Ok(PhysicalVolumeAttributes {
+                is_duplicate,
+                is_allocatable,
+                is_used,
+                is_exported,
+                is_missing,
+            })
+        }
+    }
+
+    // use our visitor to deserialize an `ActualValue`
+    deserializer.deserialize_any(AttrStringVisitor)
+}
@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde_aux::field_attributes::deserialize_number_from_string;
+
+use crate::{deserialize_optional, ResourceUUID};
+
+/// Suffixes LVM2 appends to the names of the hidden sub-volumes of a logical volume
+const SUB_VOLUME_SUFFIXES: [&str; 15] = [
+    "_rimage_", "_rmeta_", "_mimage_", "_mlog", "_tdata", "_tmeta", "_cdata", "_cmeta", "_corig",
+    "_cpool", "_cvol", "_vdata", "_imeta", "_iorig", "_wcorig",
+];
+
+/// Represents a contiguous range of extents on a [PhysicalVolume](super::PhysicalVolume)
+#[derive(Clone, Debug, Deserialize)]
+pub struct PhysicalVolumeSegment {
+    #[serde(rename = "pv_name")]
+    pub physical_volume_name: PathBuf,
+
+    #[serde(rename = "pv_uuid")]
+    pub physical_volume_uuid: ResourceUUID,
+
+    #[serde(
+        rename = "pvseg_start",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub start_extent: usize,
+
+    #[serde(
+        rename = "pvseg_size",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub extent_count: usize,
+
+    /// The name of the (possibly hidden) logical volume using this segment, if any
+    #[serde(rename = "lv_name", deserialize_with = "deserialize_optional")]
+    pub logical_volume_name: Option<String>,
+}
+
+impl PhysicalVolumeSegment {
+    /// Iff this segment is not allocated to any logical volume
+    pub fn is_free(&self) -> bool {
+        self.logical_volume_name.is_none()
+    }
+
+    /// The name of the top-level logical volume using this segment, resolving
+    /// hidden sub-volumes (e.g. `[lv_rimage_0]`) to the volume that owns them.
+    pub fn top_level_lv_name(&self) -> Option<&str> {
+        self.logical_volume_name.as_deref().map(|name| {
+            let name = name.trim_start_matches('[').trim_end_matches(']');
+
+            SUB_VOLUME_SUFFIXES
+                .iter()
+                .filter_map(|suffix| name.find(suffix))
+                .min()
+                .map(|end| &name[..end])
+                .unwrap_or(name)
+        })
+    }
+}
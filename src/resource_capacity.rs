@@ -6,7 +6,7 @@ use serde_aux::prelude::deserialize_number_from_string;
 /// Represents a valid resource capacity for LVM2
 ///
 /// A valid resource capacity must be a multiple of 512
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct ResourceCapacity(usize);
 
 impl ResourceCapacity {
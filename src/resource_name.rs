@@ -11,7 +11,7 @@ lazy_static::lazy_static! {
 ///
 /// A valid resource name is represented by the following pattern:
 /// ^[a-zA-Z0-9+_.\\-]+$
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct ResourceName(String);
 
 impl ResourceName {
@@ -11,7 +11,7 @@ lazy_static::lazy_static! {
 ///
 /// A valid resource name is represented by the following pattern:
 /// ^[a-zA-Z0-9]{6}-([a-zA-Z0-9]{4}-){5}[a-zA-Z0-9]{6}$
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ResourceUUID(String);
 
 impl Display for ResourceUUID {
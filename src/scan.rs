@@ -0,0 +1,91 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use serde_aux::field_attributes::deserialize_number_from_string;
+
+use crate::{error::LVMError, run_cmd};
+
+/// Represents a block device visible to LVM2
+#[derive(Clone, Debug, Deserialize)]
+pub struct BlockDevice {
+    #[serde(rename = "pv_name")]
+    pub path: PathBuf,
+
+    #[serde(
+        rename = "dev_size",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub capacity_bytes: usize,
+
+    /// The metadata format of the device, which is empty for devices that are not physical volumes
+    #[serde(rename = "pv_fmt")]
+    format: String,
+}
+
+impl BlockDevice {
+    /// Iff this device has been initialized as a physical volume
+    pub fn is_physical_volume(&self) -> bool {
+        !self.format.is_empty()
+    }
+}
+
+/// Rescan all devices and refresh the cached state of physical volumes.
+///
+/// This is needed after hot-adding disks before new physical volumes show up
+/// in listings.
+pub fn refresh_cache() -> Result<(), LVMError> {
+    run_cmd::<String>("pvscan", &["--cache"], None::<&str>)?;
+
+    Ok(())
+}
+
+/// Rescan all volume groups, recreating any missing device nodes for active
+/// logical volumes.
+pub fn refresh_device_nodes() -> Result<(), LVMError> {
+    run_cmd::<String>("vgscan", &["--mknodes"], None::<&str>)?;
+
+    Ok(())
+}
+
+/// Scan a newly appeared device and autoactivate the logical volumes of its
+/// volume group once it is complete, mirroring what udev does on device events.
+pub fn autoactivate(device: impl AsRef<Path>) -> Result<(), LVMError> {
+    let device = device.as_ref().to_string_lossy();
+
+    run_cmd::<String>(
+        "pvscan",
+        &["--cache", "--activate", "ay", &device],
+        None::<&str>,
+    )?;
+
+    Ok(())
+}
+
+/// List all block devices visible to LVM2, including those which are not
+/// physical volumes.
+pub fn list_devices() -> Result<Vec<BlockDevice>, LVMError> {
+    let args = vec![
+        "--nolocking",
+        "--all",
+        "--options",
+        "pv_name,dev_size,pv_fmt",
+        "--units",
+        "b",
+        "--nosuffix",
+        // Deterministically return sorted by `pv_name`
+        "--sort",
+        "pv_name",
+    ];
+
+    run_cmd("pvs", &args, Some("pv"))
+}
+
+/// List all block devices that could be initialized as new physical volumes
+pub fn list_candidate_devices() -> Result<Vec<BlockDevice>, LVMError> {
+    list_devices().map(|devices| {
+        devices
+            .into_iter()
+            .filter(|device| !device.is_physical_volume())
+            .collect()
+    })
+}
@@ -1,16 +1,33 @@
 mod vg_access_mode;
 mod vg_attributes;
+mod vg_backup;
+mod vg_capacity;
+mod vg_create_options;
+mod vg_degraded;
+mod vg_lock_type;
+mod vg_system_id;
 
 pub use vg_access_mode::*;
 pub use vg_attributes::*;
+pub use vg_backup::*;
+pub use vg_create_options::*;
+pub use vg_degraded::*;
+pub use vg_lock_type::*;
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
 
 use serde::Deserialize;
 use serde_aux::field_attributes::deserialize_number_from_string;
 
 use crate::{
+    deserialize_optional, deserialize_tags,
     error::LVMError,
-    lv::{LVCreateOptions, LogicalVolume},
-    run_cmd, ResourceCapacity, ResourceName, ResourceSelector, ResourceUUID,
+    lv::{LVCreateOptions, LogicalVolume, ThinPool, ThinPoolCreateOptions},
+    pv::PhysicalVolume,
+    run_cmd, AllocationPolicy, ResourceCapacity, ResourceName, ResourceSelector, ResourceUUID,
 };
 
 #[derive(Clone, Debug, Deserialize)]
@@ -24,12 +41,41 @@ pub struct VolumeGroup {
     #[serde(rename = "vg_size")]
     pub capacity_bytes: ResourceCapacity,
 
+    #[serde(rename = "vg_extent_size")]
+    pub extent_size_bytes: ResourceCapacity,
+
+    #[serde(
+        rename = "vg_extent_count",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub extent_count: usize,
+
+    #[serde(
+        rename = "vg_free_count",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub free_extent_count: usize,
+
     #[serde(deserialize_with = "deserialize_number_from_string")]
     pub lv_count: usize,
 
+    /// The maximum amount of allowed [LogicalVolume]s, or 0 if unlimited
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_lv: usize,
+
     #[serde(deserialize_with = "deserialize_number_from_string")]
     pub pv_count: usize,
 
+    /// The maximum amount of allowed physical volumes, or 0 if unlimited
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_pv: usize,
+
+    #[serde(
+        rename = "vg_missing_pv_count",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub missing_pv_count: usize,
+
     #[serde(deserialize_with = "deserialize_number_from_string")]
     pub snap_count: usize,
 
@@ -39,6 +85,65 @@ pub struct VolumeGroup {
     )]
     pub space_free_bytes: usize,
 
+    #[serde(
+        rename = "vg_mda_count",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub metadata_area_count: usize,
+
+    /// The amount of metadata areas in use, which excludes those ignored through `--metadataignore`
+    #[serde(
+        rename = "vg_mda_used_count",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub metadata_area_used_count: usize,
+
+    /// The free space in the smallest metadata area
+    #[serde(
+        rename = "vg_mda_free",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub metadata_area_free_bytes: usize,
+
+    /// The sequence number of the metadata, incremented on each change
+    #[serde(
+        rename = "vg_seqno",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub seqno: usize,
+
+    #[serde(rename = "vg_tags", deserialize_with = "deserialize_tags")]
+    pub tags: Vec<String>,
+
+    /// The system ID of the host owning this volume group, if any
+    #[serde(rename = "vg_systemid", deserialize_with = "deserialize_optional")]
+    pub system_id: Option<String>,
+
+    /// The lock manager used for shared volume groups
+    #[serde(
+        default,
+        rename = "vg_lock_type",
+        deserialize_with = "deserialize_lock_type"
+    )]
+    pub lock_type: Option<VGLockType>,
+
+    /// Whether the logical volumes of the group are autoactivated. Versions of
+    /// LVM2 before 2.03.12 do not report it, and always autoactivate.
+    #[serde(
+        default = "default_autoactivation",
+        rename = "vg_autoactivation",
+        deserialize_with = "deserialize_autoactivation"
+    )]
+    pub autoactivation: bool,
+
+    /// The configuration profile attached to the volume group, if any
+    #[serde(
+        default,
+        rename = "vg_profile",
+        deserialize_with = "deserialize_optional"
+    )]
+    pub profile: Option<String>,
+
     #[serde(rename = "vg_attr", deserialize_with = "deserialize_vg_attrs")]
     pub attributes: VolumeGroupAttributes,
 }
@@ -49,22 +154,16 @@ impl VolumeGroup {
         physical_devices: Vec<String>,
         opts: VGCreateOptions,
     ) -> Result<VolumeGroup, LVMError> {
-        let max_logical_volumes = opts.max_logical_volumes.unwrap_or_default().to_string();
-        let max_physical_volumes = opts.max_physical_volumes.unwrap_or_default().to_string();
-        let mut args = vec![
-            "--clustered",
-            opts.is_clustered
-                .map(|c| if c { "y" } else { "n" })
-                .unwrap_or("n"),
-            "--maxlogicalvolumes",
-            &max_logical_volumes,
-            "--maxphysicalvolumes",
-            &max_physical_volumes,
-            &opts.name,
-        ];
+        if physical_devices.is_empty() {
+            return Err(LVMError::InvalidRequest {
+                reason: "a volume group requires at least one physical device".into(),
+            });
+        }
+
+        let mut args = opts.to_args();
 
         // Add the physical devices last
-        args.extend(physical_devices.iter().map(|pv| pv.as_str()));
+        args.extend(physical_devices);
 
         // Create the volume group (has no output)
         run_cmd::<String>("vgcreate", &args, None::<&str>)?;
@@ -73,6 +172,75 @@ impl VolumeGroup {
         Self::from_id(&opts.name)
     }
 
+    /// Delete the [VolumeGroup].
+    ///
+    /// A [VolumeGroup] which still holds [LogicalVolume]s will only be deleted if
+    /// `force` is set, in which case its [LogicalVolume]s are deleted as well.
+    pub fn delete(self, force: bool) -> Result<(), LVMError> {
+        if !force {
+            let lvs = self.list_lvs()?;
+            if !lvs.is_empty() {
+                return Err(LVMError::InvalidRequest {
+                    reason: format!(
+                        "volume group {} still holds {} logical volume(s)",
+                        self.name,
+                        lvs.len()
+                    ),
+                });
+            }
+        }
+
+        let mut args = vec![];
+        if force {
+            args.push("--force");
+        }
+        args.push(&self.name);
+
+        // Remove the volume group (has no output)
+        run_cmd::<String>("vgremove", &args, None::<&str>)?;
+
+        Ok(())
+    }
+
+    /// Extend the [VolumeGroup] with a set of physical devices
+    pub fn extend(&self, physical_devices: Vec<String>) -> Result<VolumeGroup, LVMError> {
+        if physical_devices.is_empty() {
+            return Err(LVMError::InvalidRequest {
+                reason: "at least one physical device is required to extend a volume group".into(),
+            });
+        }
+
+        let mut args = vec![self.name.to_string()];
+        args.extend(physical_devices);
+
+        // Extend the volume group (has no output)
+        run_cmd::<String>("vgextend", &args, None::<&str>)?;
+
+        Self::from_id(&self.name)
+    }
+
+    /// Reduce the [VolumeGroup] by removing physical volumes from it
+    pub fn reduce(&self, target: VGReduceTarget) -> Result<VolumeGroup, LVMError> {
+        let args = match target {
+            VGReduceTarget::PhysicalDevices(physical_devices) => {
+                if physical_devices.is_empty() {
+                    return Err(LVMError::InvalidRequest {
+                        reason: "at least one physical device is required to reduce a volume group"
+                            .into(),
+                    });
+                }
+
+                [vec![self.name.to_string()], physical_devices].concat()
+            }
+            VGReduceTarget::Missing => vec!["--removemissing".into(), self.name.to_string()],
+        };
+
+        // Reduce the volume group (has no output)
+        run_cmd::<String>("vgreduce", &args, None::<&str>)?;
+
+        Self::from_id(&self.name)
+    }
+
     /// Get a specific [VolumeGroup] by its name
     pub fn from_id(volume_group: &ResourceName) -> Result<VolumeGroup, LVMError> {
         let args = vec![
@@ -113,19 +281,345 @@ impl VolumeGroup {
         run_cmd("vgs", &args, Some("vg"))
     }
 
+    /// Rename the [VolumeGroup], returning it refreshed along with its
+    /// [LogicalVolume]s, whose paths reflect the new name.
+    pub fn rename(
+        &self,
+        new_name: ResourceName,
+    ) -> Result<(VolumeGroup, Vec<LogicalVolume>), LVMError> {
+        // Rename the volume group (has no output)
+        run_cmd::<String>(
+            "vgrename",
+            &[self.name.as_str(), new_name.as_str()],
+            None::<&str>,
+        )?;
+
+        let renamed = Self::from_id(&new_name)?;
+        let lvs = renamed.list_lvs()?;
+
+        Ok((renamed, lvs))
+    }
+
+    /// Change the [VolumeGroup], refreshing its state afterwards.
+    pub fn change(&mut self, opts: VGChangeOptions) -> Result<(), LVMError> {
+        let mut args: Vec<String> = vec![];
+        if let Some(activate) = opts.activate {
+            args.extend(["--activate".into(), if activate { "y" } else { "n" }.into()]);
+        }
+
+        if let Some(mode) = opts.activation_mode {
+            args.extend(["--activationmode".into(), mode.as_arg().into()]);
+        }
+
+        if let Some(policy) = opts.allocation_policy {
+            if let AllocationPolicy::Inherited { .. } = policy {
+                return Err(LVMError::InvalidRequest {
+                    reason: "volume groups cannot inherit an allocation policy".into(),
+                });
+            }
+
+            args.extend(["--alloc".into(), policy.as_arg().into()]);
+        }
+
+        if let Some(resizeable) = opts.resizeable {
+            args.extend([
+                "--resizeable".into(),
+                if resizeable { "y" } else { "n" }.into(),
+            ]);
+        }
+
+        if let Some(max) = opts.max_logical_volumes {
+            args.extend(["--logicalvolume".into(), max.to_string()]);
+        }
+
+        if let Some(max) = opts.max_physical_volumes {
+            args.extend(["--maxphysicalvolumes".into(), max.to_string()]);
+        }
+
+        args.extend(
+            opts.add_tags
+                .into_iter()
+                .flat_map(|t| ["--addtag".into(), t]),
+        );
+        args.extend(
+            opts.remove_tags
+                .into_iter()
+                .flat_map(|t| ["--deltag".into(), t]),
+        );
+
+        if let Some(autoactivation) = opts.autoactivation {
+            args.extend([
+                "--setautoactivation".into(),
+                if autoactivation { "y" } else { "n" }.into(),
+            ]);
+        }
+
+        args.push(self.name.to_string());
+
+        // Change the volume group (has no output)
+        run_cmd::<String>("vgchange", &args, None::<&str>)?;
+
+        *self = Self::from_id(&self.name)?;
+
+        Ok(())
+    }
+
+    /// Start the lockspace of a shared [VolumeGroup], which is required before
+    /// its [LogicalVolume]s can be used on this host
+    pub fn lock_start(&self) -> Result<(), LVMError> {
+        run_cmd::<String>("vgchange", &["--lockstart", &self.name], None::<&str>)?;
+
+        Ok(())
+    }
+
+    /// Stop the lockspace of a shared [VolumeGroup], which requires all of its
+    /// [LogicalVolume]s to be inactive on this host
+    pub fn lock_stop(&self) -> Result<(), LVMError> {
+        run_cmd::<String>("vgchange", &["--lockstop", &self.name], None::<&str>)?;
+
+        Ok(())
+    }
+
+    /// Activate all [LogicalVolume]s of the volume group
+    pub fn activate_lvs(&mut self) -> Result<(), LVMError> {
+        self.set_lvs_activated(true)
+    }
+
+    /// Deactivate all [LogicalVolume]s of the volume group
+    pub fn deactivate_lvs(&mut self) -> Result<(), LVMError> {
+        self.set_lvs_activated(false)
+    }
+
+    pub fn set_lvs_activated(&mut self, should_activate: bool) -> Result<(), LVMError> {
+        self.change(VGChangeOptions {
+            activate: Some(should_activate),
+            ..Default::default()
+        })
+    }
+
+    /// Export the [VolumeGroup], making it inaccessible to this host so that its
+    /// physical volumes can be moved to another one.
+    pub fn export(&mut self) -> Result<(), LVMError> {
+        // Export the volume group (has no output)
+        run_cmd::<String>("vgexport", &[self.name.as_str()], None::<&str>)?;
+
+        *self = Self::from_id(&self.name)?;
+
+        Ok(())
+    }
+
+    /// Import a previously exported [VolumeGroup], making it accessible to this host
+    pub fn import(&mut self) -> Result<(), LVMError> {
+        // Import the volume group (has no output)
+        run_cmd::<String>("vgimport", &[self.name.as_str()], None::<&str>)?;
+
+        *self = Self::from_id(&self.name)?;
+
+        Ok(())
+    }
+
+    /// Import a [VolumeGroup] from cloned physical devices (e.g. snapshots of SAN LUNs).
+    ///
+    /// The cloned volume group is renamed and its physical volumes are given new UUIDs
+    /// so that it can coexist with the original.
+    pub fn import_clone(
+        physical_devices: Vec<String>,
+        opts: VGImportCloneOptions,
+    ) -> Result<VolumeGroup, LVMError> {
+        let device = physical_devices
+            .first()
+            .cloned()
+            .ok_or(LVMError::InvalidRequest {
+                reason: "at least one physical device is required to import a clone".into(),
+            })?;
+
+        let mut args: Vec<String> = vec![];
+        if let Some(name) = opts.base_name {
+            args.extend(["--basevgname".into(), name.to_string()]);
+        }
+
+        if opts.import {
+            args.push("--import".into());
+        }
+
+        // Add the physical devices last
+        args.extend(physical_devices);
+
+        // Import the clone (has no output)
+        run_cmd::<String>("vgimportclone", &args, None::<&str>)?;
+
+        // The final name is chosen by LVM2, so look it up from the cloned devices
+        let pv = PhysicalVolume::from_id(&device)?;
+        let name = pv.volume_group_name.ok_or(LVMError::NotFound {
+            resource: format!("volume group of {}", device),
+        })?;
+
+        Self::from_id(&name)
+    }
+
+    /// Split physical volumes, along with the [LogicalVolume]s on them, into a new
+    /// [VolumeGroup] (or an existing one, if `new_name` is already taken).
+    ///
+    /// [LogicalVolume]s may not span the boundary between the moved and the remaining
+    /// physical volumes.
+    pub fn split(
+        &self,
+        new_name: ResourceName,
+        target: VGSplitTarget,
+    ) -> Result<VolumeGroup, LVMError> {
+        let segments = PhysicalVolume::segments_for_vg(&self.name)?;
+
+        // Find which physical volumes would move to the new group
+        let (moved, logical_volume, physical_devices) = match target {
+            VGSplitTarget::PhysicalDevices(physical_devices) => {
+                if physical_devices.is_empty() {
+                    return Err(LVMError::InvalidRequest {
+                        reason: "at least one physical device is required to split a volume group"
+                            .into(),
+                    });
+                }
+
+                if let Some(device) = physical_devices.iter().find(|device| {
+                    !segments
+                        .iter()
+                        .any(|seg| seg.physical_volume_name == Path::new(device))
+                }) {
+                    return Err(LVMError::InvalidRequest {
+                        reason: format!(
+                            "physical device {} does not belong to volume group {}",
+                            device, self.name
+                        ),
+                    });
+                }
+
+                (
+                    physical_devices.iter().map(PathBuf::from).collect(),
+                    None,
+                    physical_devices,
+                )
+            }
+            VGSplitTarget::LogicalVolume(name) => {
+                let moved: HashSet<_> = segments
+                    .iter()
+                    .filter(|seg| seg.top_level_lv_name() == Some(name.as_str()))
+                    .map(|seg| seg.physical_volume_name.clone())
+                    .collect();
+
+                if moved.is_empty() {
+                    return Err(LVMError::NotFound {
+                        resource: format!("{}/{}", self.name, name),
+                    });
+                }
+
+                (moved, Some(name), vec![])
+            }
+        };
+
+        // Make sure that no logical volume would end up in both groups
+        let mut spanning: Vec<&str> = segments
+            .iter()
+            .filter(|seg| moved.contains(&seg.physical_volume_name))
+            .filter_map(|seg| seg.top_level_lv_name())
+            .filter(|lv| {
+                segments.iter().any(|seg| {
+                    seg.top_level_lv_name() == Some(lv)
+                        && !moved.contains(&seg.physical_volume_name)
+                })
+            })
+            .collect();
+
+        spanning.sort_unstable();
+        spanning.dedup();
+
+        if !spanning.is_empty() {
+            return Err(LVMError::InvalidRequest {
+                reason: format!(
+                    "logical volume(s) would span both volume groups: {}",
+                    spanning.join(", ")
+                ),
+            });
+        }
+
+        let mut args: Vec<String> = vec![];
+        if let Some(name) = logical_volume {
+            args.extend(["--name".into(), name.to_string()]);
+        }
+
+        // The physical devices to move go last
+        args.extend([self.name.to_string(), new_name.to_string()]);
+        args.extend(physical_devices);
+
+        // Split the volume group (has no output)
+        run_cmd::<String>("vgsplit", &args, None::<&str>)?;
+
+        Self::from_id(&new_name)
+    }
+
+    /// Merge another, inactive [VolumeGroup] into this one.
+    ///
+    /// Both groups must use the same physical extent size.
+    pub fn merge(&self, source: VolumeGroup) -> Result<VolumeGroup, LVMError> {
+        if *self.extent_size_bytes != *source.extent_size_bytes {
+            return Err(LVMError::InvalidRequest {
+                reason: format!(
+                    "extent sizes of {} and {} do not match: {} != {}",
+                    self.name, source.name, self.extent_size_bytes, source.extent_size_bytes
+                ),
+            });
+        }
+
+        // Merge the volume groups (has no output)
+        run_cmd::<String>(
+            "vgmerge",
+            &[self.name.as_str(), source.name.as_str()],
+            None::<&str>,
+        )?;
+
+        Self::from_id(&self.name)
+    }
+
     /// List all [LogicalVolume]s for this volume group
     pub fn list_lvs(&self) -> Result<Vec<LogicalVolume>, LVMError> {
         LogicalVolume::list_for_vg(&self.name)
     }
 
+    /// List all [PhysicalVolume]s for this volume group
+    pub fn list_pvs(&self) -> Result<Vec<PhysicalVolume>, LVMError> {
+        PhysicalVolume::list_for_vg(&self.name)
+    }
+
     /// Add a [LogicalVolume] to the volume group
     pub fn add_lv(&self, opts: LVCreateOptions) -> Result<LogicalVolume, LVMError> {
+        self.can_fit(&opts)?;
+
         LogicalVolume::create(&self.name, opts)
     }
 
+    /// Create a [ThinPool] in the volume group
+    pub fn create_thin_pool(&self, opts: ThinPoolCreateOptions) -> Result<ThinPool, LVMError> {
+        ThinPool::create(&self.name, opts)
+    }
+
+    /// List the [ThinPool]s of the volume group
+    pub fn list_thin_pools(&self) -> Result<Vec<ThinPool>, LVMError> {
+        ThinPool::list_for_vg(&self.name)
+    }
+
+    /// Rename a [LogicalVolume] of the volume group
+    pub fn rename_lv(
+        &self,
+        name: &ResourceName,
+        new_name: ResourceName,
+    ) -> Result<LogicalVolume, LVMError> {
+        let mut lv = LogicalVolume::from_id(&self.name, name)?;
+        lv.rename(new_name)?;
+
+        Ok(lv)
+    }
+
     /// Remove a [LogicalVolume] from the volume group
     pub fn remove_lv(&self, name: &ResourceName) -> Result<(), LVMError> {
-        let lv = LogicalVolume::from_id(&self.name, &name)?;
+        let lv = LogicalVolume::from_id(&self.name, name)?;
 
         lv.delete()
     }
@@ -156,16 +650,76 @@ impl ResourceSelector for VolumeGroup {
     }
 }
 
-pub struct VGCreateOptions {
-    /// The name of the [VolumeGroup]
-    name: ResourceName,
+/// Deserialize the autoactivation setting of a [VolumeGroup], which is reported as
+/// `enabled` or an empty string
+fn deserialize_autoactivation<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+
+    Ok(s == "enabled")
+}
+
+fn default_autoactivation() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct VGChangeOptions {
+    /// Whether to activate or deactivate all [LogicalVolume]s of the volume group
+    pub activate: Option<bool>,
+
+    /// How to handle [LogicalVolume]s with missing physical volumes when activating
+    pub activation_mode: Option<ActivationMode>,
+
+    /// The policy used when allocating extents for logical volumes
+    pub allocation_policy: Option<AllocationPolicy>,
+
+    /// Whether physical volumes may be added to or removed from the volume group
+    pub resizeable: Option<bool>,
+
+    /// The maximum amount of allowed [LogicalVolume]s
+    pub max_logical_volumes: Option<usize>,
+
+    /// The maximum amount of allowed physical volumes
+    pub max_physical_volumes: Option<usize>,
+
+    /// Tags to add to the volume group
+    pub add_tags: Vec<String>,
+
+    /// Tags to remove from the volume group
+    pub remove_tags: Vec<String>,
+
+    /// Whether the logical volumes of the group should be autoactivated
+    pub autoactivation: Option<bool>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct VGImportCloneOptions {
+    /// The name for the cloned volume group, with a numeric suffix appended by LVM2 if it is taken
+    pub base_name: Option<ResourceName>,
+
+    /// Whether to import the cloned volume group if it was exported
+    pub import: bool,
+}
 
-    /// Whether to enable clustered mode
-    is_clustered: Option<bool>,
+/// What to move into the new [VolumeGroup] when splitting a [VolumeGroup]
+#[derive(Clone, Debug)]
+pub enum VGSplitTarget {
+    /// Move the specified physical devices
+    PhysicalDevices(Vec<String>),
+
+    /// Move all physical volumes used by the named [LogicalVolume]
+    LogicalVolume(ResourceName),
+}
 
-    /// Whether to enforce a maximum amount of allowed [LogicalVolume]s
-    max_logical_volumes: Option<usize>,
+/// The physical volumes to remove when reducing a [VolumeGroup]
+#[derive(Clone, Debug)]
+pub enum VGReduceTarget {
+    /// Remove the specified physical devices, which must not hold any extents
+    PhysicalDevices(Vec<String>),
 
-    /// Whether to enforce a maximum amount of allowed physical volumes
-    max_physical_volumes: Option<usize>,
+    /// Remove all physical volumes which are missing from the system
+    Missing,
 }
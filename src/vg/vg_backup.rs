@@ -0,0 +1,332 @@
+use std::path::{Path, PathBuf};
+
+use crate::{error::LVMError, metadata::MetadataFile, run_raw_cmd, vg::VolumeGroup, ResourceName};
+
+/// Represents a backup of a [VolumeGroup]'s metadata, either made explicitly or
+/// archived automatically by LVM2 (in `/etc/lvm/archive`) before each change.
+#[derive(Clone, Debug)]
+pub struct MetadataBackup {
+    pub volume_group_name: ResourceName,
+
+    /// The file holding the backed up metadata
+    pub file: PathBuf,
+
+    /// The description stored with the backup, usually naming the command it was made for
+    pub description: String,
+
+    /// When the backup was made, in seconds since the unix epoch, if the backup
+    /// could be read
+    pub creation_time: Option<u64>,
+
+    /// When the backup was made, as formatted by LVM2 in local time
+    pub backup_time: String,
+}
+
+impl MetadataBackup {
+    /// Read and parse the metadata stored in this backup
+    pub fn read_metadata(&self) -> Result<MetadataFile, LVMError> {
+        let contents =
+            std::fs::read_to_string(&self.file).map_err(|err| LVMError::Internal { io: err })?;
+
+        contents
+            .parse::<MetadataFile>()
+            .map_err(|err| LVMError::MalformedOutput {
+                cause: format!("could not parse metadata backup {}", self.file.display()),
+                result: err.to_string(),
+            })
+    }
+
+    /// Restore the metadata of the [VolumeGroup] from this backup
+    pub fn restore(&self, test: bool) -> Result<MetadataRestore, LVMError> {
+        VolumeGroup::restore_metadata(
+            &self.volume_group_name,
+            VGRestoreOptions {
+                file: Some(self.file.clone()),
+                test,
+                force: false,
+            },
+        )
+    }
+}
+
+/// Represents the result of restoring a [VolumeGroup]'s metadata
+#[derive(Clone, Debug)]
+pub struct MetadataRestore {
+    pub volume_group_name: ResourceName,
+
+    /// The file the metadata was restored from, or [None] for the most recent backup
+    pub file: Option<PathBuf>,
+
+    /// Iff this was only a preview, in which case no metadata was changed
+    pub is_test: bool,
+
+    /// The messages reported by LVM2 while restoring
+    pub messages: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct VGRestoreOptions {
+    /// The file to restore from, defaulting to the most recent backup in `/etc/lvm/backup`
+    pub file: Option<PathBuf>,
+
+    /// Whether to only preview the restore without changing any metadata
+    pub test: bool,
+
+    /// Whether to restore even if the group holds thin pools, which may then need repair
+    pub force: bool,
+}
+
+impl VolumeGroup {
+    /// Back up the metadata of the [VolumeGroup] to a file
+    pub fn backup_metadata(&self, file: impl AsRef<Path>) -> Result<MetadataBackup, LVMError> {
+        let file = file.as_ref().to_string_lossy();
+        run_raw_cmd("vgcfgbackup", &["--file", &file, &self.name])?;
+
+        // Read back the details of the new backup
+        let out = run_raw_cmd("vgcfgrestore", &["--list", "--file", &file, &self.name])?;
+
+        parse_backup_list(&out)?
+            .pop()
+            .map(read_creation_time)
+            .ok_or(LVMError::NotFound {
+                resource: file.to_string(),
+            })
+    }
+
+    /// List the metadata backups and archives available for a [VolumeGroup]
+    pub fn list_metadata_backups(
+        volume_group: &ResourceName,
+    ) -> Result<Vec<MetadataBackup>, LVMError> {
+        let out = run_raw_cmd("vgcfgrestore", &["--list", volume_group.as_str()])?;
+
+        Ok(parse_backup_list(&out)?
+            .into_iter()
+            .map(read_creation_time)
+            .collect())
+    }
+
+    /// Restore the metadata of a [VolumeGroup] from a backup
+    pub fn restore_metadata(
+        volume_group: &ResourceName,
+        opts: VGRestoreOptions,
+    ) -> Result<MetadataRestore, LVMError> {
+        let mut args: Vec<String> = vec![];
+        if let Some(file) = &opts.file {
+            args.extend(["--file".into(), file.to_string_lossy().into()]);
+        }
+
+        if opts.test {
+            args.push("--test".into());
+        }
+
+        if opts.force {
+            args.push("--force".into());
+        }
+
+        args.push(volume_group.to_string());
+
+        let out = run_raw_cmd("vgcfgrestore", &args)?;
+
+        Ok(MetadataRestore {
+            volume_group_name: volume_group.clone(),
+            file: opts.file,
+            is_test: opts.test,
+            messages: out
+                .lines()
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty())
+                .map(|line| line.into())
+                .collect(),
+        })
+    }
+}
+
+/// Parse the output of `vgcfgrestore --list`
+///
+/// Each backup is printed as a block of `Key: value` lines, starting with `File:`.
+fn parse_backup_list(out: &str) -> Result<Vec<MetadataBackup>, LVMError> {
+    let mut backups = vec![];
+    let mut current: Option<PartialBackup> = None;
+
+    for (key, value) in out
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim(), value.trim()))
+    {
+        if key == "File" {
+            if let Some(backup) = current.take() {
+                backups.push(backup.finish()?);
+            }
+
+            current = Some(PartialBackup {
+                file: value.into(),
+                ..Default::default()
+            });
+
+            continue;
+        }
+
+        let Some(backup) = current.as_mut() else {
+            continue;
+        };
+
+        match key {
+            "VG name" => backup.volume_group_name = Some(value.into()),
+            "Description" => backup.description = value.into(),
+            "Backup Time" => backup.backup_time = value.into(),
+            _ => (),
+        }
+    }
+
+    if let Some(backup) = current.take() {
+        backups.push(backup.finish()?);
+    }
+
+    Ok(backups)
+}
+
+/// Read when a backup was made from the backup itself, since LVM2 only lists it
+/// in local time
+fn read_creation_time(mut backup: MetadataBackup) -> MetadataBackup {
+    backup.creation_time = backup
+        .read_metadata()
+        .ok()
+        .and_then(|metadata| metadata.creation_time);
+
+    backup
+}
+
+/// A [MetadataBackup] which is still being parsed
+#[derive(Default)]
+struct PartialBackup {
+    file: PathBuf,
+    volume_group_name: Option<String>,
+    description: String,
+    backup_time: String,
+}
+
+impl PartialBackup {
+    fn finish(self) -> Result<MetadataBackup, LVMError> {
+        let volume_group_name = self
+            .volume_group_name
+            .ok_or(LVMError::MalformedOutput {
+                cause: "metadata backup is missing its volume group name".into(),
+                result: self.file.to_string_lossy().into(),
+            })
+            .and_then(|name| {
+                ResourceName::try_from(name).map_err(|err| LVMError::MalformedOutput {
+                    cause: "invalid volume group name in metadata backup".into(),
+                    result: err.to_string(),
+                })
+            })?;
+
+        Ok(MetadataBackup {
+            volume_group_name,
+            file: self.file,
+            description: self.description,
+            creation_time: None,
+            backup_time: self.backup_time,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // In the format printed by `vgcfgrestore --list vg0` on LVM2 2.03
+    const OUTPUT: &str = "   
+  File:		/etc/lvm/archive/vg0_00000-1624589383.vg
+  VG name:    	vg0
+  Description:	Created *before* executing 'vgcreate vg0 /dev/sdb /dev/sdc'
+  Backup Time:	Sat Oct 17 09:05:12 2026
+
+   
+  File:		/etc/lvm/archive/vg0_00001-704281905.vg
+  VG name:    	vg0
+  Description:	Created *before* executing 'lvcreate --addtag tier:gold --name data --size 1G vg0'
+  Backup Time:	Sat Oct 17 09:06:40 2026
+
+   
+  File:		/etc/lvm/backup/vg0
+  VG name:    	vg0
+  Description:	Created *after* executing 'lvcreate --addtag tier:gold --name data --size 1G vg0'
+  Backup Time:	Sat Oct 17 09:06:40 2026
+
+";
+
+    #[test]
+    fn parses_every_block() {
+        let backups = parse_backup_list(OUTPUT).unwrap();
+        assert_eq!(backups.len(), 3);
+
+        let files: Vec<_> = backups.iter().map(|backup| backup.file.clone()).collect();
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("/etc/lvm/archive/vg0_00000-1624589383.vg"),
+                PathBuf::from("/etc/lvm/archive/vg0_00001-704281905.vg"),
+                PathBuf::from("/etc/lvm/backup/vg0"),
+            ]
+        );
+
+        assert!(backups
+            .iter()
+            .all(|backup| backup.volume_group_name.as_str() == "vg0"));
+    }
+
+    #[test]
+    fn keeps_colons_in_values() {
+        let backups = parse_backup_list(OUTPUT).unwrap();
+
+        assert_eq!(
+            backups[1].description,
+            "Created *before* executing 'lvcreate --addtag tier:gold --name data --size 1G vg0'"
+        );
+        assert_eq!(backups[1].backup_time, "Sat Oct 17 09:06:40 2026");
+    }
+
+    #[test]
+    fn parses_empty_list() {
+        assert!(parse_backup_list("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_block_without_volume_group() {
+        let out = "  File:\t\t/etc/lvm/backup/vg0\n  Backup Time:\tSat Oct 17 09:06:40 2026\n";
+
+        assert!(matches!(
+            parse_backup_list(out),
+            Err(LVMError::MalformedOutput { .. })
+        ));
+    }
+
+    #[test]
+    fn reads_creation_time_from_backup() {
+        let dir = std::env::temp_dir().join(format!("lvm2_cmd-backup-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let file = dir.join("vg0");
+        std::fs::write(
+            &file,
+            "contents = \"Text Format Volume Group\"\nversion = 1\ncreation_time = 1792228000\n\n\
+             vg0 {\n\tid = \"Fq3t1C-aYtM-kP2v-Ew0R-dG7s-Lq9B-x1Zc7m\"\n\tseqno = 1\n\
+             \textent_size = 8192\n}\n",
+        )
+        .unwrap();
+
+        let out = format!(
+            "  File:\t\t{}\n  VG name:    \tvg0\n  Backup Time:\tSat Oct 17 09:06:40 2026\n",
+            file.display()
+        );
+        let backup = read_creation_time(parse_backup_list(&out).unwrap().pop().unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(backup.creation_time, Some(1792228000));
+        assert_eq!(backup.backup_time, "Sat Oct 17 09:06:40 2026");
+
+        // Unreadable backups only keep the time listed by LVM2
+        let missing = read_creation_time(parse_backup_list(OUTPUT).unwrap().remove(0));
+        assert_eq!(missing.creation_time, None);
+    }
+}
@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::LVMError,
+    lv::{LVCreateOptions, LVSegmentType},
+    pv::PhysicalVolume,
+    vg::VolumeGroup,
+    AllocationPolicy,
+};
+
+impl VolumeGroup {
+    /// Check whether a [LogicalVolume](crate::lv::LogicalVolume) with the specified
+    /// options could be allocated in the [VolumeGroup].
+    ///
+    /// The requested capacity is rounded up to whole extents for every stripe, and
+    /// the extra images and metadata of the segment type are accounted for. Free
+    /// space is only counted on allocatable physical volumes, and is split between
+    /// images the way the allocation policy of the [VolumeGroup] requires.
+    ///
+    /// Returns [LVMError::InvalidRequest] if the layout needs more physical volumes
+    /// than are available, or [LVMError::InsufficientSpace] with the shortfall if
+    /// it won't fit.
+    pub fn can_fit(&self, opts: &LVCreateOptions) -> Result<(), LVMError> {
+        opts.segment_type.validate()?;
+
+        let extent_size = *self.extent_size_bytes;
+        let units = allocation_units(&opts.segment_type, *opts.capacity_bytes, extent_size);
+
+        // Collect the free space of each allocatable physical volume
+        let allocatable: Vec<_> = PhysicalVolume::list_for_vg(&self.name)?
+            .into_iter()
+            .filter(|pv| pv.attributes.is_allocatable && !pv.attributes.is_missing)
+            .map(|pv| pv.name)
+            .collect();
+
+        // Each image must live on its own physical volume, unless anywhere goes
+        let anywhere = matches!(
+            self.attributes.allocation_policy,
+            AllocationPolicy::Anyware { .. }
+        );
+
+        if !anywhere && allocatable.len() < units.len() {
+            return Err(LVMError::InvalidRequest {
+                reason: format!(
+                    "layout requires {} allocatable physical volumes, but {} only has {}",
+                    units.len(),
+                    self.name,
+                    allocatable.len()
+                ),
+            });
+        }
+
+        let mut free_segments: HashMap<_, Vec<usize>> = HashMap::new();
+        for segment in PhysicalVolume::segments_for_vg(&self.name)? {
+            if segment.is_free() && allocatable.contains(&segment.physical_volume_name) {
+                free_segments
+                    .entry(segment.physical_volume_name)
+                    .or_default()
+                    .push(segment.extent_count);
+            }
+        }
+
+        check_fit(
+            &self.name,
+            &self.attributes.allocation_policy,
+            &units,
+            free_segments.values(),
+            extent_size,
+        )
+    }
+}
+
+/// Check whether the units fit in the free segments of each physical volume,
+/// following the allocation policy
+fn check_fit<'a>(
+    volume_group: &str,
+    policy: &AllocationPolicy,
+    units: &[usize],
+    free_segments: impl Iterator<Item = &'a Vec<usize>>,
+    extent_size: usize,
+) -> Result<(), LVMError> {
+    let required: usize = units.iter().sum();
+
+    let available = match policy {
+        // Images may share physical volumes, so all free space is usable
+        AllocationPolicy::Anyware { .. } => free_segments.flatten().sum::<usize>().min(required),
+
+        // Each image must fit in a single free segment of its own physical volume
+        AllocationPolicy::Contiguous { .. } => allocate_separately(
+            units,
+            free_segments
+                .map(|segments| segments.iter().copied().max().unwrap_or(0))
+                .collect(),
+        ),
+
+        // Each image may span physical volumes, but never share one with another image
+        _ => allocate_spanning(
+            units,
+            free_segments
+                .map(|segments| segments.iter().sum())
+                .collect(),
+        ),
+    };
+
+    if available >= required {
+        return Ok(());
+    }
+
+    Err(LVMError::InsufficientSpace {
+        volume_group: volume_group.into(),
+        required_bytes: required * extent_size,
+        available_bytes: available * extent_size,
+        shortfall_bytes: (required - available) * extent_size,
+    })
+}
+
+/// Split a request into the amount of extents needed by each separately allocated
+/// image, including its metadata
+fn allocation_units(
+    segment_type: &LVSegmentType,
+    capacity_bytes: usize,
+    extent_size: usize,
+) -> Vec<usize> {
+    let stripes = segment_type.data_stripes().max(1);
+    let images = segment_type.images().max(1);
+
+    // Every stripe holds the same amount of whole extents
+    let extents = capacity_bytes
+        .div_ceil(extent_size)
+        .next_multiple_of(stripes);
+    let mut units = vec![extents / stripes; images];
+
+    match segment_type {
+        // The mirror log lives on its own physical volume
+        LVSegmentType::Mirror { .. } => units.push(segment_type.metadata_extents()),
+
+        // RAID metadata is allocated next to each image, along with the
+        // dm-integrity metadata if enabled
+        LVSegmentType::Raid { integrity, .. } => {
+            let mut per_image = segment_type.metadata_extents() / images;
+            if *integrity {
+                per_image += integrity_metadata_bytes(units[0] * extent_size).div_ceil(extent_size);
+            }
+
+            units.iter_mut().for_each(|unit| *unit += per_image);
+        }
+        _ => (),
+    }
+
+    units
+}
+
+/// The size of the dm-integrity metadata LVM2 allocates for an image, which is
+/// 4MiB for every started 500MiB of data
+fn integrity_metadata_bytes(image_bytes: usize) -> usize {
+    const MIB: usize = 1024 * 1024;
+
+    (image_bytes / (500 * MIB) + 1) * 4 * MIB
+}
+
+/// Greedily assign the free extents of distinct physical volumes to each unit,
+/// largest first, returning the amount of extents that could be allocated
+fn allocate_separately(units: &[usize], mut free: Vec<usize>) -> usize {
+    let mut units = units.to_vec();
+    units.sort_unstable_by(|a, b| b.cmp(a));
+    free.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut free = free.into_iter();
+    units
+        .into_iter()
+        .map(|unit| free.next().unwrap_or(0).min(unit))
+        .sum()
+}
+
+/// Assign the free extents of each physical volume, largest first, to the unit
+/// still needing the most, returning the amount of extents that could be allocated
+fn allocate_spanning(units: &[usize], mut free: Vec<usize>) -> usize {
+    let mut remaining = units.to_vec();
+    free.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut allocated = 0;
+    for extents in free {
+        let Some(unit) = remaining.iter_mut().max() else {
+            break;
+        };
+
+        let taken = extents.min(*unit);
+        *unit -= taken;
+        allocated += taken;
+    }
+
+    allocated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lv::{Raid5Layout, RaidLevel};
+
+    const EXTENT: usize = 4 * 1024 * 1024;
+
+    fn raid(level: RaidLevel, stripes: usize, mirrors: usize) -> LVSegmentType {
+        LVSegmentType::Raid {
+            level,
+            stripes,
+            mirrors,
+            stripe_size: None,
+            region_size: None,
+            integrity: false,
+        }
+    }
+
+    fn fit(
+        policy: AllocationPolicy,
+        units: &[usize],
+        free_segments: &[Vec<usize>],
+    ) -> Result<(), LVMError> {
+        check_fit("vg", &policy, units, free_segments.iter(), EXTENT)
+    }
+
+    fn shortfall(result: Result<(), LVMError>) -> (usize, usize, usize) {
+        match result {
+            Err(LVMError::InsufficientSpace {
+                required_bytes,
+                available_bytes,
+                shortfall_bytes,
+                ..
+            }) => (required_bytes, available_bytes, shortfall_bytes),
+            other => panic!("expected insufficient space, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn linear_rounds_up_to_whole_extents() {
+        let units = allocation_units(&LVSegmentType::Linear, 10 * 1024 * 1024, EXTENT);
+        assert_eq!(units, vec![3]);
+
+        let units = allocation_units(&LVSegmentType::Linear, 8 * 1024 * 1024, EXTENT);
+        assert_eq!(units, vec![2]);
+    }
+
+    #[test]
+    fn striped_rounds_up_to_whole_stripes() {
+        let striped = LVSegmentType::Striped { stripes: 2 };
+        assert_eq!(
+            allocation_units(&striped, 10 * 1024 * 1024, EXTENT),
+            vec![2, 2]
+        );
+
+        let striped = LVSegmentType::Striped { stripes: 3 };
+        assert_eq!(
+            allocation_units(&striped, 10 * 1024 * 1024, EXTENT),
+            vec![1, 1, 1]
+        );
+    }
+
+    #[test]
+    fn mirror_adds_images_and_a_log() {
+        let mirror = LVSegmentType::Mirror { mirrors: 2 };
+        assert_eq!(
+            allocation_units(&mirror, 8 * 1024 * 1024, EXTENT),
+            vec![2, 2, 2, 1]
+        );
+    }
+
+    #[test]
+    fn raid5_adds_parity_and_metadata() {
+        let raid5 = raid(RaidLevel::Raid5(Raid5Layout::default()), 3, 0);
+        assert_eq!(
+            allocation_units(&raid5, 12 * 1024 * 1024, EXTENT),
+            vec![2, 2, 2, 2]
+        );
+    }
+
+    #[test]
+    fn raid_integrity_adds_metadata_per_image() {
+        let mut raid5 = raid(RaidLevel::Raid5(Raid5Layout::default()), 3, 0);
+        if let LVSegmentType::Raid { integrity, .. } = &mut raid5 {
+            *integrity = true;
+        }
+
+        assert_eq!(
+            allocation_units(&raid5, 12 * 1024 * 1024, EXTENT),
+            vec![3, 3, 3, 3]
+        );
+
+        // Images of 1500MiB need (1500 / 500 + 1) * 4MiB of integrity metadata
+        let mut raid1 = raid(RaidLevel::Raid1, 0, 1);
+        if let LVSegmentType::Raid { integrity, .. } = &mut raid1 {
+            *integrity = true;
+        }
+
+        assert_eq!(
+            allocation_units(&raid1, 1500 * 1024 * 1024, EXTENT),
+            vec![375 + 1 + 4, 375 + 1 + 4]
+        );
+    }
+
+    #[test]
+    fn raid10_mirrors_each_stripe() {
+        let raid10 = raid(RaidLevel::Raid10, 2, 1);
+        assert_eq!(
+            allocation_units(&raid10, 16 * 1024 * 1024, EXTENT),
+            vec![3, 3, 3, 3]
+        );
+    }
+
+    #[test]
+    fn anywhere_pools_all_free_space() {
+        assert!(fit(
+            AllocationPolicy::Anyware { locked: false },
+            &[2, 2, 2, 2],
+            &[vec![5], vec![3]]
+        )
+        .is_ok());
+
+        assert_eq!(
+            shortfall(fit(
+                AllocationPolicy::Anyware { locked: false },
+                &[2, 2, 2, 2],
+                &[vec![2, 3]]
+            )),
+            (8 * EXTENT, 5 * EXTENT, 3 * EXTENT)
+        );
+    }
+
+    #[test]
+    fn contiguous_needs_a_single_segment_per_image() {
+        assert!(fit(
+            AllocationPolicy::Contiguous { locked: false },
+            &[2, 2],
+            &[vec![2], vec![1, 5]]
+        )
+        .is_ok());
+
+        // Enough space in total, but split over two segments
+        assert_eq!(
+            shortfall(fit(
+                AllocationPolicy::Contiguous { locked: false },
+                &[4],
+                &[vec![3, 3]]
+            )),
+            (4 * EXTENT, 3 * EXTENT, EXTENT)
+        );
+    }
+
+    #[test]
+    fn normal_spans_volumes_but_keeps_images_apart() {
+        assert!(fit(
+            AllocationPolicy::Normal { locked: false },
+            &[4],
+            &[vec![3], vec![1]]
+        )
+        .is_ok());
+
+        // Both images cannot share the only physical volume
+        assert_eq!(
+            shortfall(fit(
+                AllocationPolicy::Normal { locked: false },
+                &[3, 3],
+                &[vec![6]]
+            )),
+            (6 * EXTENT, 3 * EXTENT, 3 * EXTENT)
+        );
+
+        assert_eq!(
+            shortfall(fit(
+                AllocationPolicy::Inherited { locked: false },
+                &[3, 3],
+                &[vec![4], vec![1]]
+            )),
+            (6 * EXTENT, 4 * EXTENT, 2 * EXTENT)
+        );
+    }
+}
@@ -0,0 +1,286 @@
+use regex::Regex;
+
+use crate::{error::LVMError, vg::VGLockType, AllocationPolicy, ResourceCapacity, ResourceName};
+
+lazy_static::lazy_static! {
+    static ref TAG_REGEX: Regex = Regex::new("^[a-zA-Z0-9_+.\\-/=!:&#]+$").expect("could not compile tag enforcement regex!");
+}
+
+/// The maximum length of a tag, as enforced by LVM2
+const MAX_TAG_LENGTH: usize = 1024;
+
+/// The maximum length of a system ID, as enforced by LVM2
+const MAX_SYSTEM_ID_LENGTH: usize = 128;
+
+/// The amount of metadata copies LVM2 should maintain for a volume group
+#[derive(Clone, Debug)]
+pub enum VGMetadataCopies {
+    /// Keep a copy of the metadata on every physical volume
+    All,
+
+    /// Let LVM2 manage the metadata areas as configured on each physical volume
+    Unmanaged,
+
+    /// Keep exactly this many copies of the metadata
+    Count(usize),
+}
+
+impl VGMetadataCopies {
+    pub(crate) fn as_arg(&self) -> String {
+        match self {
+            VGMetadataCopies::All => "all".into(),
+            VGMetadataCopies::Unmanaged => "unmanaged".into(),
+            VGMetadataCopies::Count(count) => count.to_string(),
+        }
+    }
+}
+
+/// Options for creating a [VolumeGroup](super::VolumeGroup)
+///
+/// Use [VGCreateOptions::builder] to construct a validated set of options.
+#[derive(Clone, Debug)]
+pub struct VGCreateOptions {
+    /// The name of the volume group
+    pub(crate) name: ResourceName,
+
+    /// The size of each physical extent
+    physical_extent_size: Option<ResourceCapacity>,
+
+    /// The policy used when allocating extents for logical volumes
+    allocation_policy: Option<AllocationPolicy>,
+
+    /// Tags to add to the volume group
+    tags: Vec<String>,
+
+    /// Whether to enforce a maximum amount of allowed logical volumes
+    max_logical_volumes: Option<usize>,
+
+    /// Whether to enforce a maximum amount of allowed physical volumes
+    max_physical_volumes: Option<usize>,
+
+    /// The amount of metadata copies to keep
+    metadata_copies: Option<VGMetadataCopies>,
+
+    /// Whether the volume group is shared between hosts using lvmlockd
+    is_shared: bool,
+
+    /// The lock manager used by lvmlockd for a shared volume group
+    lock_type: Option<VGLockType>,
+
+    /// The system ID of the host owning the volume group
+    system_id: Option<String>,
+
+    /// Whether the logical volumes of the group should be autoactivated
+    autoactivation: Option<bool>,
+}
+
+impl VGCreateOptions {
+    /// Start building options for a volume group with the specified name
+    pub fn builder(name: ResourceName) -> VGCreateOptionsBuilder {
+        VGCreateOptionsBuilder {
+            opts: VGCreateOptions {
+                name,
+                physical_extent_size: None,
+                allocation_policy: None,
+                tags: vec![],
+                max_logical_volumes: None,
+                max_physical_volumes: None,
+                metadata_copies: None,
+                is_shared: false,
+                lock_type: None,
+                system_id: None,
+                autoactivation: None,
+            },
+        }
+    }
+
+    /// The name of the volume group to create
+    pub fn name(&self) -> &ResourceName {
+        &self.name
+    }
+
+    /// Convert the options into arguments for `vgcreate`
+    pub(crate) fn to_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = vec![];
+
+        if let Some(size) = &self.physical_extent_size {
+            args.extend(["--physicalextentsize".into(), format!("{}B", size)]);
+        }
+
+        if let Some(policy) = &self.allocation_policy {
+            args.extend(["--alloc".into(), policy.as_arg().into()]);
+        }
+
+        args.extend(
+            self.tags
+                .iter()
+                .flat_map(|t| ["--addtag".into(), t.clone()]),
+        );
+
+        if let Some(max) = self.max_logical_volumes {
+            args.extend(["--maxlogicalvolumes".into(), max.to_string()]);
+        }
+
+        if let Some(max) = self.max_physical_volumes {
+            args.extend(["--maxphysicalvolumes".into(), max.to_string()]);
+        }
+
+        if let Some(copies) = &self.metadata_copies {
+            args.extend(["--vgmetadatacopies".into(), copies.as_arg()]);
+        }
+
+        if self.is_shared {
+            args.push("--shared".into());
+        }
+
+        if let Some(lock_type) = &self.lock_type {
+            args.extend(["--locktype".into(), lock_type.as_arg().into()]);
+        }
+
+        if let Some(system_id) = &self.system_id {
+            args.extend(["--systemid".into(), system_id.clone()]);
+        }
+
+        if let Some(autoactivation) = self.autoactivation {
+            args.extend([
+                "--setautoactivation".into(),
+                if autoactivation { "y" } else { "n" }.into(),
+            ]);
+        }
+
+        args.push(self.name.to_string());
+
+        args
+    }
+}
+
+/// Builder for [VGCreateOptions]
+#[derive(Clone, Debug)]
+pub struct VGCreateOptionsBuilder {
+    opts: VGCreateOptions,
+}
+
+impl VGCreateOptionsBuilder {
+    /// Set the size of each physical extent, which must be a power of 2
+    pub fn physical_extent_size(mut self, size: ResourceCapacity) -> Self {
+        self.opts.physical_extent_size = Some(size);
+        self
+    }
+
+    /// Set the policy used when allocating extents for logical volumes
+    pub fn allocation_policy(mut self, policy: AllocationPolicy) -> Self {
+        self.opts.allocation_policy = Some(policy);
+        self
+    }
+
+    /// Add a tag to the volume group
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.opts.tags.push(tag.into());
+        self
+    }
+
+    /// Set the maximum amount of logical volumes allowed in the volume group
+    pub fn max_logical_volumes(mut self, max: usize) -> Self {
+        self.opts.max_logical_volumes = Some(max);
+        self
+    }
+
+    /// Set the maximum amount of physical volumes allowed in the volume group
+    pub fn max_physical_volumes(mut self, max: usize) -> Self {
+        self.opts.max_physical_volumes = Some(max);
+        self
+    }
+
+    /// Set the amount of metadata copies to keep
+    pub fn metadata_copies(mut self, copies: VGMetadataCopies) -> Self {
+        self.opts.metadata_copies = Some(copies);
+        self
+    }
+
+    /// Whether the volume group is shared between hosts using lvmlockd
+    pub fn shared(mut self, is_shared: bool) -> Self {
+        self.opts.is_shared = is_shared;
+        self
+    }
+
+    /// Set the lock manager used by lvmlockd, which requires the volume group to be shared
+    pub fn lock_type(mut self, lock_type: VGLockType) -> Self {
+        self.opts.lock_type = Some(lock_type);
+        self
+    }
+
+    /// Set the system ID of the host owning the volume group
+    pub fn system_id(mut self, system_id: impl Into<String>) -> Self {
+        self.opts.system_id = Some(system_id.into());
+        self
+    }
+
+    /// Whether the logical volumes of the group should be autoactivated
+    pub fn autoactivation(mut self, autoactivation: bool) -> Self {
+        self.opts.autoactivation = Some(autoactivation);
+        self
+    }
+
+    /// Validate and build the [VGCreateOptions]
+    pub fn build(self) -> Result<VGCreateOptions, LVMError> {
+        let opts = self.opts;
+
+        if let Some(size) = &opts.physical_extent_size {
+            if !size.is_power_of_two() {
+                return Err(LVMError::InvalidRequest {
+                    reason: format!("physical extent size must be a power of 2: {}", size),
+                });
+            }
+        }
+
+        if let Some(AllocationPolicy::Inherited { .. }) = opts.allocation_policy {
+            return Err(LVMError::InvalidRequest {
+                reason: "volume groups cannot inherit an allocation policy".into(),
+            });
+        }
+
+        if let Some(tag) = opts
+            .tags
+            .iter()
+            .find(|t| t.len() > MAX_TAG_LENGTH || !TAG_REGEX.is_match(t))
+        {
+            return Err(LVMError::InvalidRequest {
+                reason: format!(
+                    "tag must be valid for LVM2 ([a-zA-Z0-9_+.-/=!:&#]): {}",
+                    tag
+                ),
+            });
+        }
+
+        if opts.lock_type == Some(VGLockType::Clvm) {
+            return Err(LVMError::InvalidRequest {
+                reason: "clvm volume groups can no longer be created".into(),
+            });
+        }
+
+        if opts.lock_type.is_some() && !opts.is_shared {
+            return Err(LVMError::InvalidRequest {
+                reason: "a lock type can only be set for shared volume groups".into(),
+            });
+        }
+
+        if let Some(system_id) = &opts.system_id {
+            if opts.is_shared {
+                return Err(LVMError::InvalidRequest {
+                    reason: "shared volume groups cannot have a system ID".into(),
+                });
+            }
+
+            if system_id.is_empty() || system_id.len() > MAX_SYSTEM_ID_LENGTH {
+                return Err(LVMError::InvalidRequest {
+                    reason: format!(
+                        "system ID must be between 1 and {} characters: {}",
+                        MAX_SYSTEM_ID_LENGTH, system_id
+                    ),
+                });
+            }
+        }
+
+        Ok(opts)
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::HashSet;
+
+use crate::{
+    error::LVMError,
+    lv::LogicalVolume,
+    pv::PhysicalVolume,
+    run_cmd,
+    vg::{VGChangeOptions, VolumeGroup},
+    ResourceName, ResourceUUID,
+};
+
+/// How to handle [LogicalVolume]s with missing physical volumes when activating
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ActivationMode {
+    /// Only activate [LogicalVolume]s which have all of their physical volumes
+    Complete,
+
+    /// Also activate RAID and mirror [LogicalVolume]s which are missing redundant images
+    Degraded,
+
+    /// Activate all [LogicalVolume]s, replacing missing extents with an error target
+    Partial,
+}
+
+impl ActivationMode {
+    pub(crate) fn as_arg(&self) -> &'static str {
+        match self {
+            ActivationMode::Complete => "complete",
+            ActivationMode::Degraded => "degraded",
+            ActivationMode::Partial => "partial",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RemoveMissingOptions {
+    /// Only remove the mirror images on missing physical volumes, keeping everything else
+    pub mirrors_only: bool,
+
+    /// Whether to also drop [LogicalVolume]s which cannot be kept without the missing
+    /// physical volumes
+    pub remove_partial_lvs: bool,
+
+    /// Explicit confirmation that data on the missing physical volumes may be lost
+    pub confirmed: bool,
+}
+
+/// Represents what was dropped from a [VolumeGroup] when removing missing physical volumes
+#[derive(Clone, Debug)]
+pub struct MissingPVRemoval {
+    /// The UUIDs of the physical volumes which were removed
+    pub removed_pvs: Vec<ResourceUUID>,
+
+    /// The names of the [LogicalVolume]s which were dropped along with them
+    pub dropped_lvs: Vec<ResourceName>,
+
+    /// The state of the [VolumeGroup] after the removal
+    pub volume_group: VolumeGroup,
+}
+
+impl VolumeGroup {
+    /// List the physical volumes of the [VolumeGroup] which are missing from the system
+    pub fn missing_pvs(&self) -> Result<Vec<PhysicalVolume>, LVMError> {
+        self.list_pvs().map(|pvs| {
+            pvs.into_iter()
+                .filter(|pv| pv.attributes.is_missing)
+                .collect()
+        })
+    }
+
+    /// List the [LogicalVolume]s which have extents on missing physical volumes
+    pub fn affected_lvs(&self) -> Result<Vec<LogicalVolume>, LVMError> {
+        let missing: Vec<_> = self.missing_pvs()?.into_iter().map(|pv| pv.uuid).collect();
+
+        if missing.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let segments = PhysicalVolume::segments_for_vg(&self.name)?;
+        let affected: HashSet<_> = segments
+            .iter()
+            .filter(|seg| missing.contains(&seg.physical_volume_uuid))
+            .filter_map(|seg| seg.top_level_lv_name())
+            .collect();
+
+        self.list_lvs().map(|lvs| {
+            lvs.into_iter()
+                .filter(|lv| affected.contains(lv.name.as_str()))
+                .collect()
+        })
+    }
+
+    /// Activate all [LogicalVolume]s of the volume group using a specific [ActivationMode]
+    pub fn activate_lvs_with_mode(&mut self, mode: ActivationMode) -> Result<(), LVMError> {
+        self.change(VGChangeOptions {
+            activate: Some(true),
+            activation_mode: Some(mode),
+            ..Default::default()
+        })
+    }
+
+    /// Remove all physical volumes which are missing from the system.
+    ///
+    /// Any data on the missing physical volumes is lost, so this requires
+    /// [RemoveMissingOptions::confirmed] to be set.
+    pub fn remove_missing_pvs(
+        &self,
+        opts: RemoveMissingOptions,
+    ) -> Result<MissingPVRemoval, LVMError> {
+        if !opts.confirmed {
+            return Err(LVMError::InvalidRequest {
+                reason: format!(
+                    "removing missing physical volumes from {} must be confirmed",
+                    self.name
+                ),
+            });
+        }
+
+        let missing_before = self.missing_pvs()?;
+        let lvs_before = self.list_lvs()?;
+
+        let mut args = vec!["--removemissing"];
+        if opts.mirrors_only {
+            args.push("--mirrorsonly");
+        }
+
+        if opts.remove_partial_lvs {
+            args.push("--force");
+        }
+
+        args.push(&self.name);
+
+        // Reduce the volume group (has no output)
+        run_cmd::<String>("vgreduce", &args, None::<&str>)?;
+
+        let volume_group = Self::from_id(&self.name)?;
+        let missing_after: Vec<_> = volume_group
+            .missing_pvs()?
+            .into_iter()
+            .map(|pv| pv.uuid)
+            .collect();
+
+        let lvs_after: Vec<_> = volume_group
+            .list_lvs()?
+            .into_iter()
+            .map(|lv| lv.name)
+            .collect();
+
+        Ok(MissingPVRemoval {
+            removed_pvs: missing_before
+                .into_iter()
+                .map(|pv| pv.uuid)
+                .filter(|uuid| !missing_after.contains(uuid))
+                .collect(),
+            dropped_lvs: lvs_before
+                .into_iter()
+                .map(|lv| lv.name)
+                .filter(|name| !lvs_after.contains(name))
+                .collect(),
+            volume_group,
+        })
+    }
+}
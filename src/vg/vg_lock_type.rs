@@ -0,0 +1,78 @@
+use serde::{Deserialize, Deserializer};
+
+/// The lock manager used by lvmlockd for a shared [VolumeGroup](super::VolumeGroup)
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VGLockType {
+    Sanlock,
+    Dlm,
+    Idm,
+
+    /// A clustered volume group from before lvmlockd, which was locked by clvmd
+    Clvm,
+}
+
+impl VGLockType {
+    pub(crate) fn as_arg(&self) -> &'static str {
+        match self {
+            VGLockType::Sanlock => "sanlock",
+            VGLockType::Dlm => "dlm",
+            VGLockType::Idm => "idm",
+            VGLockType::Clvm => "clvm",
+        }
+    }
+}
+
+/// Deserialize the lock type of a volume group, which LVM2 reports as empty or
+/// `none` for local volume groups
+pub(crate) fn deserialize_lock_type<'de, D>(deserializer: D) -> Result<Option<VGLockType>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+
+    match s.as_str() {
+        "" | "none" => Ok(None),
+        "sanlock" => Ok(Some(VGLockType::Sanlock)),
+        "dlm" => Ok(Some(VGLockType::Dlm)),
+        "idm" => Ok(Some(VGLockType::Idm)),
+        "clvm" => Ok(Some(VGLockType::Clvm)),
+        _ => Err(serde::de::Error::custom(format!(
+            "unknown lock type: {}",
+            s
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Report {
+        #[serde(deserialize_with = "deserialize_lock_type")]
+        vg_lock_type: Option<VGLockType>,
+    }
+
+    fn lock_type(value: &str) -> Option<VGLockType> {
+        let report = format!("{{ \"vg_lock_type\": \"{}\" }}", value);
+
+        serde_json::from_str::<Report>(&report)
+            .unwrap()
+            .vg_lock_type
+    }
+
+    #[test]
+    fn deserializes_local_volume_groups() {
+        assert_eq!(lock_type(""), None);
+        assert_eq!(lock_type("none"), None);
+    }
+
+    #[test]
+    fn deserializes_lock_managers() {
+        assert_eq!(lock_type("sanlock"), Some(VGLockType::Sanlock));
+        assert_eq!(lock_type("dlm"), Some(VGLockType::Dlm));
+        assert_eq!(lock_type("idm"), Some(VGLockType::Idm));
+        assert_eq!(lock_type("clvm"), Some(VGLockType::Clvm));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{error::LVMError, run_cmd, run_raw_cmd, vg::VolumeGroup};
+
+impl VolumeGroup {
+    /// Get the system ID of this host, if one is configured
+    pub fn local_system_id() -> Result<Option<String>, LVMError> {
+        let out = run_raw_cmd("systemid", &[] as &[&str])?;
+
+        Ok(out
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("system ID:"))
+            .map(|id| id.trim())
+            .filter(|id| !id.is_empty())
+            .map(|id| id.into()))
+    }
+
+    /// List the [VolumeGroup]s which are owned by other hosts.
+    ///
+    /// These are normally hidden, and cannot be used until their system ID is changed.
+    pub fn list_foreign() -> Result<Vec<VolumeGroup>, LVMError> {
+        let local = Self::local_system_id()?;
+        let args = vec![
+            "--nolocking",
+            "--foreign",
+            "--options",
+            "+vg_all",
+            "--units",
+            "b",
+            "--nosuffix",
+            // Deterministically return sorted by `vg_name`
+            "--sort",
+            "vg_name",
+        ];
+
+        run_cmd("vgs", &args, Some("vg")).map(|vgs: Vec<VolumeGroup>| {
+            vgs.into_iter()
+                .filter(|vg| vg.is_foreign(local.as_deref()))
+                .collect()
+        })
+    }
+
+    /// Iff this [VolumeGroup] is owned by a host other than the one with the specified system ID
+    pub fn is_foreign(&self, local_system_id: Option<&str>) -> bool {
+        match &self.system_id {
+            Some(system_id) => Some(system_id.as_str()) != local_system_id,
+            None => false,
+        }
+    }
+
+    /// Set the system ID of the host owning this [VolumeGroup], or remove it to
+    /// allow access from any host.
+    ///
+    /// Warning: Handing a [VolumeGroup] to another host while it is in use here
+    /// can lead to corruption.
+    pub fn set_system_id(&mut self, system_id: Option<&str>) -> Result<(), LVMError> {
+        run_cmd::<String>(
+            "vgchange",
+            &["--systemid", system_id.unwrap_or_default(), &self.name],
+            None::<&str>,
+        )?;
+
+        // The group may now belong to another host, so it has to be looked up as foreign
+        let args = vec![
+            "--nolocking",
+            "--foreign",
+            "--options",
+            "+vg_all",
+            "--units",
+            "b",
+            "--nosuffix",
+            &self.name,
+        ];
+
+        *self = run_cmd("vgs", &args, Some("vg")).and_then(|mut vgs| {
+            vgs.pop().ok_or(LVMError::NotFound {
+                resource: self.name.to_string(),
+            })
+        })?;
+
+        Ok(())
+    }
+}